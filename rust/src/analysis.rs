@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::memory::read_padded;
+use crate::opcode;
+
+// code together with the result of the JUMPDEST pre-pass, built once per
+// contract and shared between every execution of it through a
+// `BytecodeCache`
+pub struct AnalyzedBytecode {
+    code: Vec<u8>,
+    // one bit per code byte, set when the byte is a JUMPDEST opcode
+    jumpdests: Vec<u64>,
+}
+
+impl AnalyzedBytecode {
+    pub fn new(code: &[u8]) -> Self {
+        AnalyzedBytecode {
+            code: code.to_vec(),
+            jumpdests: jumpdest_bitmap(code),
+        }
+    }
+
+    pub fn code(&self) -> &[u8] {
+        &self.code
+    }
+
+    // copy `out.len()` bytes starting at `offset`, anything past the end of
+    // the code reads as zero
+    pub fn read(&self, offset: usize, out: &mut [u8]) {
+        read_padded(&self.code, offset, out);
+    }
+
+    pub fn is_jumpdest(&self, pc: usize) -> bool {
+        pc < self.code.len() && self.jumpdests[pc / 64] & (1 << (pc % 64)) != 0
+    }
+}
+
+// walk the code once, stepping over PUSH immediates so a 0x5b inside push
+// data is never marked
+fn jumpdest_bitmap(code: &[u8]) -> Vec<u64> {
+    let mut bitmap = vec![0u64; code.len().div_ceil(64)];
+    let mut pc = 0;
+
+    while pc < code.len() {
+        let op = code[pc];
+
+        if op == opcode::JUMPDEST {
+            bitmap[pc / 64] |= 1 << (pc % 64);
+        }
+
+        // an immediate that runs past the end just ends the walk
+        if (opcode::PUSH1..=opcode::PUSH32).contains(&op) {
+            pc += (op - opcode::PUSH1 + 1) as usize;
+        }
+
+        pc += 1;
+    }
+
+    bitmap
+}
+
+// analyzed code keyed by its keccak hash, which the caller already has, e.g.
+// from `Account::code_hash`, so a lookup never hashes the code again
+#[derive(Default)]
+pub struct BytecodeCache {
+    entries: HashMap<[u8; 32], Arc<AnalyzedBytecode>>,
+}
+
+impl BytecodeCache {
+    pub fn new() -> Self {
+        BytecodeCache::default()
+    }
+
+    pub fn get(&self, hash: &[u8; 32]) -> Option<Arc<AnalyzedBytecode>> {
+        self.entries.get(hash).cloned()
+    }
+
+    // analyze the code only the first time its hash is seen, `code_hash`
+    // must be the keccak256 of `code`
+    pub fn get_or_analyze(&mut self, code_hash: [u8; 32], code: &[u8]) -> Arc<AnalyzedBytecode> {
+        self.entries
+            .entry(code_hash)
+            .or_insert_with(|| Arc::new(AnalyzedBytecode::new(code)))
+            .clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use primitive_types::{H160, U256};
+
+pub type Address = H160;
+
+// everything about the transaction and block the code runs in
+#[derive(Debug, Clone, Default)]
+pub struct Environment {
+    pub tx: Transaction,
+    pub block: Block,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Transaction {
+    pub to: Address,
+    pub from: Address,
+    pub origin: Address,
+    pub gasprice: U256,
+    pub value: U256,
+    // calldata
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Block {
+    pub coinbase: Address,
+    pub timestamp: U256,
+    pub number: U256,
+    // PREVRANDAO since the merge
+    pub difficulty: U256,
+    pub gaslimit: U256,
+    pub chainid: U256,
+    pub basefee: U256,
+    // hashes of earlier blocks by number, BLOCKHASH only sees the 256 before
+    // this one and gives zero for any block missing here
+    pub hashes: HashMap<U256, [u8; 32]>,
+}
+
+impl Block {
+    pub(crate) fn hash_of(&self, number: U256) -> U256 {
+        let recent = number < self.number && self.number - number <= U256::from(256);
+
+        match self.hashes.get(&number) {
+            Some(hash) if recent => U256::from_big_endian(hash),
+            _ => U256::zero(),
+        }
+    }
+}
+
+// what one call frame sees as its own address, caller, value and calldata,
+// the top frame takes them from the transaction
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CallContext {
+    pub(crate) address: Address,
+    pub(crate) caller: Address,
+    pub(crate) value: U256,
+    pub(crate) input: Vec<u8>,
+}
+
+impl CallContext {
+    pub(crate) fn from_tx(tx: &Transaction) -> Self {
+        CallContext {
+            address: tx.to,
+            caller: tx.from,
+            value: tx.value,
+            input: tx.data.clone(),
+        }
+    }
+}
+
+// addresses sit in the low 20 bytes of a stack word
+pub(crate) fn address_to_word(address: Address) -> U256 {
+    U256::from_big_endian(address.as_bytes())
+}
+
+// the low 20 bytes of the word, the rest is ignored
+pub(crate) fn word_to_address(word: U256) -> Address {
+    let mut bytes = [0u8; 32];
+    word.to_big_endian(&mut bytes);
+    Address::from_slice(&bytes[12..])
+}
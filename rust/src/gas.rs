@@ -0,0 +1,138 @@
+use primitive_types::U256;
+
+use crate::HaltReason;
+
+// static cost tiers from the yellow paper
+pub(crate) const ZERO: u64 = 0;
+pub(crate) const JUMPDEST: u64 = 1;
+pub(crate) const BASE: u64 = 2;
+pub(crate) const VERYLOW: u64 = 3;
+pub(crate) const LOW: u64 = 5;
+pub(crate) const MID: u64 = 8;
+pub(crate) const HIGH: u64 = 10;
+pub(crate) const BLOCKHASH: u64 = 20;
+pub(crate) const KECCAK256: u64 = 30;
+pub(crate) const LOG: u64 = 375;
+
+// access lists are not tracked, so every account access is charged as cold
+pub(crate) const ACCOUNT_ACCESS: u64 = 2600;
+pub(crate) const SLOAD: u64 = 2100;
+
+// SSTORE, without tracking the value a slot had at the start of the
+// transaction each write is priced against the current value
+pub(crate) const SSTORE_NOOP: u64 = 100;
+pub(crate) const SSTORE_SET: u64 = 20000;
+pub(crate) const SSTORE_RESET: u64 = 2900;
+// SSTORE is refused when no more than the call stipend is left
+pub(crate) const SSTORE_SENTRY: u64 = CALL_STIPEND;
+
+// CALL, the stipend is handed to the callee for free when value is sent
+pub(crate) const CALL_VALUE: u64 = 9000;
+pub(crate) const NEW_ACCOUNT: u64 = 25000;
+pub(crate) const CALL_STIPEND: u64 = 2300;
+
+// dynamic parts
+pub(crate) const EXP_BYTE: u64 = 50;
+pub(crate) const KECCAK256_WORD: u64 = 6;
+pub(crate) const COPY_WORD: u64 = 3;
+pub(crate) const LOG_TOPIC: u64 = 375;
+pub(crate) const LOG_DATA: u64 = 8;
+
+// EXP pays for every byte of the exponent
+pub(crate) fn exp_cost(exponent: U256) -> u64 {
+    EXP_BYTE * exponent.bits().div_ceil(8) as u64
+}
+
+// KECCAK256 pays for every word hashed
+pub(crate) fn keccak256_cost(len: usize) -> u64 {
+    KECCAK256_WORD * len.div_ceil(32) as u64
+}
+
+// CALLDATACOPY, CODECOPY, RETURNDATACOPY and MCOPY pay per word copied
+pub(crate) fn copy_cost(len: usize) -> u64 {
+    COPY_WORD * len.div_ceil(32) as u64
+}
+
+// LOGn pays for every topic and every byte of data, a length too large to
+// price saturates to a cost nothing can pay
+pub(crate) fn log_cost(topics: usize, len: usize) -> u64 {
+    LOG_DATA.saturating_mul(len as u64).saturating_add(LOG_TOPIC * topics as u64)
+}
+
+pub(crate) fn sstore_cost(current: U256, new: U256) -> u64 {
+    if current == new {
+        SSTORE_NOOP
+    } else if current.is_zero() {
+        SSTORE_SET
+    } else {
+        SSTORE_RESET
+    }
+}
+
+// all but one 64th of the gas left can be passed on to a call
+pub(crate) fn max_call_gas(remaining: u64) -> u64 {
+    remaining - remaining / 64
+}
+
+pub(crate) struct Gas {
+    limit: u64,
+    remaining: u64,
+    // false when nothing is charged at all, see `evm_unmetered`
+    metered: bool,
+}
+
+impl Gas {
+    pub(crate) fn new(limit: u64) -> Self {
+        Gas {
+            limit,
+            remaining: limit,
+            metered: true,
+        }
+    }
+
+    pub(crate) fn unmetered() -> Self {
+        Gas {
+            limit: u64::MAX,
+            remaining: u64::MAX,
+            metered: false,
+        }
+    }
+
+    pub(crate) fn is_metered(&self) -> bool {
+        self.metered
+    }
+
+    pub(crate) fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    pub(crate) fn used(&self) -> u64 {
+        self.limit - self.remaining
+    }
+
+    pub(crate) fn record_cost(&mut self, cost: u64) -> Result<(), HaltReason> {
+        if !self.metered {
+            return Ok(());
+        }
+
+        if cost > self.remaining {
+            return Err(HaltReason::OutOfGas);
+        }
+
+        self.remaining -= cost;
+        Ok(())
+    }
+
+    // give back what a call frame did not use
+    pub(crate) fn return_unused(&mut self, amount: u64) {
+        if self.metered {
+            self.remaining += amount;
+        }
+    }
+
+    pub(crate) fn consume_all(&mut self) {
+        if self.metered {
+            self.remaining = 0;
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Div, Neg, Rem};
+
+use primitive_types::U256;
+
+// a stack word read as a two's complement signed number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct I256(U256);
+
+impl I256 {
+    pub const ZERO: I256 = I256(U256::zero());
+    pub const MIN: I256 = I256(U256([0, 0, 0, 0x8000_0000_0000_0000]));
+    pub const MAX: I256 = I256(U256([u64::MAX, u64::MAX, u64::MAX, 0x7fff_ffff_ffff_ffff]));
+
+    pub fn from_u256(value: U256) -> Self {
+        I256(value)
+    }
+
+    pub fn into_u256(self) -> U256 {
+        self.0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0.bit(255)
+    }
+
+    // the magnitude, which always fits unsigned, even for MIN
+    pub fn abs(self) -> U256 {
+        if self.is_negative() {
+            (-self).0
+        } else {
+            self.0
+        }
+    }
+
+    // arithmetic shift right, the vacated high bits copy the sign bit
+    pub fn sar(self, shift: U256) -> Self {
+        let negative = self.is_negative();
+
+        if shift >= U256::from(255) {
+            return if negative { I256(U256::MAX) } else { I256::ZERO };
+        }
+
+        let shift = shift.as_usize();
+        let shifted = self.0 >> shift;
+
+        if negative && shift > 0 {
+            I256(shifted | (U256::MAX << (256 - shift)))
+        } else {
+            I256(shifted)
+        }
+    }
+
+    fn from_magnitude(magnitude: U256, negative: bool) -> Self {
+        let value = I256(magnitude);
+        if negative {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+// wrapping, so negating MIN gives MIN back
+impl Neg for I256 {
+    type Output = I256;
+
+    fn neg(self) -> I256 {
+        I256((!self.0).overflowing_add(U256::one()).0)
+    }
+}
+
+// SDIV semantics: rounds towards zero, division by zero gives zero and
+// MIN / -1 overflows back to MIN
+impl Div for I256 {
+    type Output = I256;
+
+    fn div(self, rhs: I256) -> I256 {
+        if rhs.0.is_zero() {
+            return I256::ZERO;
+        }
+
+        let quotient = self.abs() / rhs.abs();
+        I256::from_magnitude(quotient, self.is_negative() ^ rhs.is_negative())
+    }
+}
+
+// SMOD semantics: the result takes the sign of the dividend and a zero
+// modulus gives zero
+impl Rem for I256 {
+    type Output = I256;
+
+    fn rem(self, rhs: I256) -> I256 {
+        if rhs.0.is_zero() {
+            return I256::ZERO;
+        }
+
+        let remainder = self.abs() % rhs.abs();
+        I256::from_magnitude(remainder, self.is_negative())
+    }
+}
+
+impl Ord for I256 {
+    fn cmp(&self, other: &I256) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            // same sign, so the unsigned order is the signed one
+            _ => self.0.cmp(&other.0),
+        }
+    }
+}
+
+impl PartialOrd for I256 {
+    fn partial_cmp(&self, other: &I256) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for I256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_negative() {
+            write!(f, "-{}", self.abs())
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
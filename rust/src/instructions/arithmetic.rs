@@ -0,0 +1,162 @@
+use primitive_types::{U256, U512};
+
+use crate::gas;
+use crate::i256::I256;
+use crate::interpreter::Interpreter;
+use crate::HaltReason;
+
+// ADD or ADD (overflow)
+pub(crate) fn add(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+
+    interp.stack.push(first.overflowing_add(second).0)?;
+    Ok(())
+}
+
+// MUL or MUL (overflow)
+pub(crate) fn mul(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+
+    interp.stack.push(first.overflowing_mul(second).0)?;
+    Ok(())
+}
+
+// SUB or SUB (underflow)
+pub(crate) fn sub(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+
+    interp.stack.push(first.overflowing_sub(second).0)?;
+    Ok(())
+}
+
+// DIV
+pub(crate) fn div(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+
+    let result = if second.is_zero() { U256::zero() } else { first / second };
+
+    interp.stack.push(result)?;
+    Ok(())
+}
+
+// SDIV
+pub(crate) fn sdiv(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = I256::from_u256(interp.stack.pop()?);
+    let second = I256::from_u256(interp.stack.pop()?);
+
+    interp.stack.push((first / second).into_u256())?;
+    Ok(())
+}
+
+// MOD
+pub(crate) fn rem(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+
+    let result = if second.is_zero() { U256::zero() } else { first % second };
+
+    interp.stack.push(result)?;
+    Ok(())
+}
+
+// SMOD
+pub(crate) fn smod(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = I256::from_u256(interp.stack.pop()?);
+    let second = I256::from_u256(interp.stack.pop()?);
+
+    interp.stack.push((first % second).into_u256())?;
+    Ok(())
+}
+
+// ADDMOD or ADDMOD (wrapped)
+pub(crate) fn addmod(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+    let third = interp.stack.pop()?;
+
+    // the sum can carry into bit 256, so it is taken in 512 bits
+    let result = if third.is_zero() {
+        U256::zero()
+    } else {
+        let sum = U512::from(first) + U512::from(second);
+        narrow(sum % U512::from(third))
+    };
+
+    interp.stack.push(result)?;
+    Ok(())
+}
+
+// MULMOD or MULMOD (wrapped)
+pub(crate) fn mulmod(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+    let third = interp.stack.pop()?;
+
+    let result = if third.is_zero() {
+        U256::zero()
+    } else {
+        narrow(first.full_mul(second) % U512::from(third))
+    };
+
+    interp.stack.push(result)?;
+    Ok(())
+}
+
+// a remainder modulo a 256-bit number always fits back into 256 bits
+fn narrow(value: U512) -> U256 {
+    U256::try_from(value).expect("remainder fits in 256 bits")
+}
+
+// EXP
+pub(crate) fn exp(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let base = interp.stack.pop()?;
+    let exponent = interp.stack.pop()?;
+
+    interp.gas.record_cost(gas::exp_cost(exponent))?;
+
+    interp.stack.push(wrapping_pow(base, exponent))?;
+    Ok(())
+}
+
+// square and multiply, walking the exponent bits from the lowest one,
+// everything wraps modulo 2^256
+fn wrapping_pow(base: U256, exponent: U256) -> U256 {
+    let mut result = U256::one();
+    let mut square = base;
+
+    for i in 0..exponent.bits() {
+        if exponent.bit(i) {
+            result = result.overflowing_mul(square).0;
+        }
+        square = square.overflowing_mul(square).0;
+    }
+
+    result
+}
+
+// SIGNEXTEND
+pub(crate) fn signextend(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let index = interp.stack.pop()?;
+    let value = interp.stack.pop()?;
+
+    // byte 31 is already the sign byte of the whole word
+    let result = if index >= U256::from(31) {
+        value
+    } else {
+        let sign_bit = index.as_usize() * 8 + 7;
+        let mask = (U256::one() << (sign_bit + 1)) - 1;
+
+        if value.bit(sign_bit) {
+            value | !mask
+        } else {
+            value & mask
+        }
+    };
+
+    interp.stack.push(result)?;
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use primitive_types::U256;
+
+use crate::i256::I256;
+use crate::interpreter::Interpreter;
+use crate::HaltReason;
+
+pub(super) fn bool_to_word(value: bool) -> U256 {
+    if value {
+        U256::one()
+    } else {
+        U256::zero()
+    }
+}
+
+// LT
+pub(crate) fn lt(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+
+    interp.stack.push(bool_to_word(first < second))?;
+    Ok(())
+}
+
+// GT
+pub(crate) fn gt(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+
+    interp.stack.push(bool_to_word(first > second))?;
+    Ok(())
+}
+
+// SLT
+pub(crate) fn slt(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = I256::from_u256(interp.stack.pop()?);
+    let second = I256::from_u256(interp.stack.pop()?);
+
+    interp.stack.push(bool_to_word(first < second))?;
+    Ok(())
+}
+
+// SGT
+pub(crate) fn sgt(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = I256::from_u256(interp.stack.pop()?);
+    let second = I256::from_u256(interp.stack.pop()?);
+
+    interp.stack.push(bool_to_word(first > second))?;
+    Ok(())
+}
+
+// EQ
+pub(crate) fn eq(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+
+    interp.stack.push(bool_to_word(first == second))?;
+    Ok(())
+}
+
+// ISZERO
+pub(crate) fn iszero(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+
+    interp.stack.push(bool_to_word(first.is_zero()))?;
+    Ok(())
+}
+
+// AND
+pub(crate) fn and(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+
+    interp.stack.push(first & second)?;
+    Ok(())
+}
+
+// OR
+pub(crate) fn or(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+
+    interp.stack.push(first | second)?;
+    Ok(())
+}
+
+// XOR
+pub(crate) fn xor(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+    let second = interp.stack.pop()?;
+
+    interp.stack.push(first ^ second)?;
+    Ok(())
+}
+
+// NOT
+pub(crate) fn not(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let first = interp.stack.pop()?;
+
+    interp.stack.push(!first)?;
+    Ok(())
+}
+
+// BYTE
+pub(crate) fn byte(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let i = interp.stack.pop()?;
+    let value = interp.stack.pop()?;
+
+    // byte 0 is the most significant one
+    let result = if i >= U256::from(32) {
+        U256::zero()
+    } else {
+        U256::from(value.byte(31 - i.as_usize()))
+    };
+
+    interp.stack.push(result)?;
+    Ok(())
+}
+
+// SHL
+pub(crate) fn shl(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let shift = interp.stack.pop()?;
+    let value = interp.stack.pop()?;
+
+    let result = if shift >= U256::from(256) { U256::zero() } else { value << shift };
+
+    interp.stack.push(result)?;
+    Ok(())
+}
+
+// SHR
+pub(crate) fn shr(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let shift = interp.stack.pop()?;
+    let value = interp.stack.pop()?;
+
+    let result = if shift >= U256::from(256) { U256::zero() } else { value >> shift };
+
+    interp.stack.push(result)?;
+    Ok(())
+}
+
+// SAR
+pub(crate) fn sar(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let shift = interp.stack.pop()?;
+    let value = I256::from_u256(interp.stack.pop()?);
+
+    interp.stack.push(value.sar(shift).into_u256())?;
+    Ok(())
+}
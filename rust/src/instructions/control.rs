@@ -0,0 +1,68 @@
+use primitive_types::U256;
+
+use crate::interpreter::Interpreter;
+use crate::HaltReason;
+
+// STOP
+pub(crate) fn stop(_interp: &mut Interpreter) -> Result<(), HaltReason> {
+    Err(HaltReason::Stop)
+}
+
+// INVALID and every unassigned opcode
+pub(crate) fn invalid(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    Err(HaltReason::InvalidOpcode(interp.current_opcode()))
+}
+
+// JUMPDEST
+pub(crate) fn jumpdest(_interp: &mut Interpreter) -> Result<(), HaltReason> {
+    Ok(())
+}
+
+// JUMP
+pub(crate) fn jump(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let destination = interp.stack.pop()?;
+
+    if !interp.check_valid_jump_location(destination) {
+        return Err(HaltReason::InvalidJump(destination));
+    }
+
+    interp.pc = destination.as_usize();
+    Ok(())
+}
+
+// JUMPI
+pub(crate) fn jumpi(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let destination = interp.stack.pop()?;
+    let condition = interp.stack.pop()?;
+
+    if condition.is_zero() {
+        return Ok(());
+    }
+
+    if !interp.check_valid_jump_location(destination) {
+        return Err(HaltReason::InvalidJump(destination));
+    }
+
+    interp.pc = destination.as_usize();
+    Ok(())
+}
+
+// PC
+pub(crate) fn pc(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    // the pc has already moved past this opcode
+    interp.stack.push(U256::from(interp.pc - 1))?;
+    Ok(())
+}
+
+// GAS
+pub(crate) fn gas(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    // the cost of GAS itself has already been taken
+    let remaining = if interp.gas.is_metered() {
+        U256::from(interp.gas.remaining())
+    } else {
+        U256::MAX
+    };
+
+    interp.stack.push(remaining)?;
+    Ok(())
+}
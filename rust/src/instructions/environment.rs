@@ -0,0 +1,170 @@
+use primitive_types::U256;
+
+use crate::env::address_to_word;
+use crate::gas;
+use crate::interpreter::Interpreter;
+use crate::memory::{read_padded, tail, to_range, to_usize_saturating};
+use crate::HaltReason;
+
+// ADDRESS
+pub(crate) fn address(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(address_to_word(interp.context.address))?;
+    Ok(())
+}
+
+// ORIGIN
+pub(crate) fn origin(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(address_to_word(interp.env.tx.origin))?;
+    Ok(())
+}
+
+// CALLER
+pub(crate) fn caller(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(address_to_word(interp.context.caller))?;
+    Ok(())
+}
+
+// CALLVALUE
+pub(crate) fn callvalue(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(interp.context.value)?;
+    Ok(())
+}
+
+// CALLDATALOAD
+pub(crate) fn calldataload(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let offset = to_usize_saturating(interp.stack.pop()?);
+
+    let mut word = [0u8; 32];
+    read_padded(&interp.context.input, offset, &mut word);
+
+    interp.stack.push(U256::from_big_endian(&word))?;
+    Ok(())
+}
+
+// CALLDATASIZE
+pub(crate) fn calldatasize(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(U256::from(interp.context.input.len()))?;
+    Ok(())
+}
+
+// CALLDATACOPY
+pub(crate) fn calldatacopy(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let destination = interp.stack.pop()?;
+    let offset = to_usize_saturating(interp.stack.pop()?);
+    let len = interp.stack.pop()?;
+
+    let (destination, len) = to_range(destination, len)?;
+
+    interp.gas.record_cost(gas::copy_cost(len))?;
+    interp.expand_memory(destination, len)?;
+
+    let source = tail(&interp.context.input, offset);
+    interp.memory.copy_from(source, destination, len);
+    Ok(())
+}
+
+// CODESIZE
+pub(crate) fn codesize(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(U256::from(interp.bytecode.code().len()))?;
+    Ok(())
+}
+
+// CODECOPY
+pub(crate) fn codecopy(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let destination = interp.stack.pop()?;
+    let offset = to_usize_saturating(interp.stack.pop()?);
+    let len = interp.stack.pop()?;
+
+    let (destination, len) = to_range(destination, len)?;
+
+    interp.gas.record_cost(gas::copy_cost(len))?;
+    interp.expand_memory(destination, len)?;
+
+    let source = tail(interp.bytecode.code(), offset);
+    interp.memory.copy_from(source, destination, len);
+    Ok(())
+}
+
+// GASPRICE
+pub(crate) fn gasprice(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(interp.env.tx.gasprice)?;
+    Ok(())
+}
+
+// RETURNDATASIZE
+pub(crate) fn returndatasize(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(U256::from(interp.return_data.len()))?;
+    Ok(())
+}
+
+// RETURNDATACOPY
+pub(crate) fn returndatacopy(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let destination = interp.stack.pop()?;
+    let offset = interp.stack.pop()?;
+    let len = interp.stack.pop()?;
+
+    // unlike the other copies, reading past the end is an error
+    let (end, overflow) = offset.overflowing_add(len);
+    if overflow || end > U256::from(interp.return_data.len()) {
+        return Err(HaltReason::ReturnDataOutOfBounds);
+    }
+
+    let (destination, len) = to_range(destination, len)?;
+
+    interp.gas.record_cost(gas::copy_cost(len))?;
+    interp.expand_memory(destination, len)?;
+
+    let source = tail(&interp.return_data, offset.as_usize());
+    interp.memory.copy_from(source, destination, len);
+    Ok(())
+}
+
+// BLOCKHASH
+pub(crate) fn blockhash(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let number = interp.stack.pop()?;
+
+    interp.stack.push(interp.env.block.hash_of(number))?;
+    Ok(())
+}
+
+// COINBASE
+pub(crate) fn coinbase(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(address_to_word(interp.env.block.coinbase))?;
+    Ok(())
+}
+
+// TIMESTAMP
+pub(crate) fn timestamp(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(interp.env.block.timestamp)?;
+    Ok(())
+}
+
+// NUMBER
+pub(crate) fn number(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(interp.env.block.number)?;
+    Ok(())
+}
+
+// PREVRANDAO, DIFFICULTY before the merge
+pub(crate) fn prevrandao(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(interp.env.block.difficulty)?;
+    Ok(())
+}
+
+// GASLIMIT
+pub(crate) fn gaslimit(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(interp.env.block.gaslimit)?;
+    Ok(())
+}
+
+// CHAINID
+pub(crate) fn chainid(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(interp.env.block.chainid)?;
+    Ok(())
+}
+
+// BASEFEE
+pub(crate) fn basefee(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(interp.env.block.basefee)?;
+    Ok(())
+}
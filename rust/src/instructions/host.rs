@@ -0,0 +1,120 @@
+use primitive_types::U256;
+
+use crate::env::word_to_address;
+use crate::gas;
+use crate::interpreter::Interpreter;
+use crate::memory::{tail, to_range, to_usize_saturating};
+use crate::opcode;
+use crate::{HaltReason, Log};
+
+// BALANCE
+pub(crate) fn balance(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let address = word_to_address(interp.stack.pop()?);
+
+    interp.stack.push(interp.state.balance(&address))?;
+    Ok(())
+}
+
+// SELFBALANCE
+pub(crate) fn selfbalance(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(interp.state.balance(&interp.context.address))?;
+    Ok(())
+}
+
+// EXTCODESIZE
+pub(crate) fn extcodesize(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let address = word_to_address(interp.stack.pop()?);
+
+    interp.stack.push(U256::from(interp.state.code(&address).len()))?;
+    Ok(())
+}
+
+// EXTCODECOPY
+pub(crate) fn extcodecopy(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let address = word_to_address(interp.stack.pop()?);
+    let destination = interp.stack.pop()?;
+    let offset = to_usize_saturating(interp.stack.pop()?);
+    let len = interp.stack.pop()?;
+
+    let (destination, len) = to_range(destination, len)?;
+
+    interp.gas.record_cost(gas::copy_cost(len))?;
+    interp.expand_memory(destination, len)?;
+
+    let source = tail(interp.state.code(&address), offset);
+    interp.memory.copy_from(source, destination, len);
+    Ok(())
+}
+
+// EXTCODEHASH
+pub(crate) fn extcodehash(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let address = word_to_address(interp.stack.pop()?);
+
+    // zero for accounts that do not exist, an existing account without code
+    // still hashes to keccak256 of the empty string
+    let hash = match interp.state.get(&address) {
+        Some(account) => U256::from_big_endian(&account.code_hash()),
+        None => U256::zero(),
+    };
+
+    interp.stack.push(hash)?;
+    Ok(())
+}
+
+// SLOAD
+pub(crate) fn sload(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let key = interp.stack.pop()?;
+
+    let value = interp.state.storage(&interp.context.address, key);
+    interp.stack.push(value)?;
+    Ok(())
+}
+
+// SSTORE
+pub(crate) fn sstore(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    if interp.is_static {
+        return Err(HaltReason::StaticViolation);
+    }
+
+    let key = interp.stack.pop()?;
+    let value = interp.stack.pop()?;
+
+    if interp.gas.remaining() <= gas::SSTORE_SENTRY {
+        return Err(HaltReason::OutOfGas);
+    }
+
+    let address = interp.context.address;
+    let current = interp.state.storage(&address, key);
+    interp.gas.record_cost(gas::sstore_cost(current, value))?;
+
+    interp.state.set_storage(address, key, value);
+    Ok(())
+}
+
+// LOG0 to LOG4
+pub(crate) fn log(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    if interp.is_static {
+        return Err(HaltReason::StaticViolation);
+    }
+
+    let offset = interp.stack.pop()?;
+    let len = interp.stack.pop()?;
+
+    let count = (interp.current_opcode() - opcode::LOG0) as usize;
+    let mut topics = Vec::with_capacity(count);
+    for _ in 0..count {
+        topics.push(interp.stack.pop()?);
+    }
+
+    let (offset, len) = to_range(offset, len)?;
+
+    interp.gas.record_cost(gas::log_cost(count, len))?;
+    interp.expand_memory(offset, len)?;
+
+    interp.logs.push(Log {
+        address: interp.context.address,
+        data: interp.memory.slice(offset, len).to_vec(),
+        topics,
+    });
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use primitive_types::U256;
+
+use crate::interpreter::Interpreter;
+use crate::gas;
+use crate::memory::{to_range, to_usize};
+use crate::HaltReason;
+
+// MLOAD
+pub(crate) fn mload(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let offset = to_usize(interp.stack.pop()?)?;
+
+    interp.expand_memory(offset, 32)?;
+
+    let value = interp.memory.load_word(offset);
+    interp.stack.push(value)?;
+    Ok(())
+}
+
+// MSTORE
+pub(crate) fn mstore(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let offset = to_usize(interp.stack.pop()?)?;
+    let value = interp.stack.pop()?;
+
+    interp.expand_memory(offset, 32)?;
+
+    interp.memory.store_word(offset, value);
+    Ok(())
+}
+
+// MSTORE8
+pub(crate) fn mstore8(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let offset = to_usize(interp.stack.pop()?)?;
+    let value = interp.stack.pop()?;
+
+    interp.expand_memory(offset, 1)?;
+
+    // only the lowest byte is stored
+    interp.memory.store_byte(offset, value.byte(0));
+    Ok(())
+}
+
+// MSIZE
+pub(crate) fn msize(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(U256::from(interp.memory.len()))?;
+    Ok(())
+}
+
+// MCOPY
+pub(crate) fn mcopy(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let destination = interp.stack.pop()?;
+    let source = interp.stack.pop()?;
+    let len = interp.stack.pop()?;
+
+    let (destination, len) = to_range(destination, len)?;
+    let (source, _) = to_range(source, U256::from(len))?;
+
+    interp.gas.record_cost(gas::copy_cost(len))?;
+    interp.expand_memory(source.max(destination), len)?;
+
+    interp.memory.copy_within(source, destination, len);
+    Ok(())
+}
@@ -0,0 +1,151 @@
+mod arithmetic;
+mod bitwise;
+mod control;
+mod environment;
+mod host;
+mod memory;
+mod stack;
+mod system;
+
+use crate::gas;
+use crate::interpreter::Interpreter;
+use crate::HaltReason;
+use crate::opcode::*;
+
+// every opcode handler gets the interpreter with the pc already pointing
+// past the opcode byte, and returns Err to stop execution
+pub(crate) type Instruction = fn(&mut Interpreter) -> Result<(), HaltReason>;
+
+#[derive(Clone, Copy)]
+pub(crate) struct OpInfo {
+    pub(crate) execute: Instruction,
+    // how many stack items the opcode consumes, checked before it runs
+    pub(crate) inputs: u8,
+    // the fixed part of the cost, charged before the handler runs
+    pub(crate) gas: u64,
+}
+
+impl OpInfo {
+    const fn new(execute: Instruction, inputs: u8, gas: u64) -> Self {
+        OpInfo {
+            execute,
+            inputs,
+            gas,
+        }
+    }
+}
+
+// one entry per opcode byte, unassigned bytes all go to `control::invalid`
+pub(crate) static TABLE: [OpInfo; 256] = table();
+
+const fn table() -> [OpInfo; 256] {
+    let mut table = [OpInfo::new(control::invalid, 0, gas::ZERO); 256];
+
+    table[STOP as usize] = OpInfo::new(control::stop, 0, gas::ZERO);
+    table[ADD as usize] = OpInfo::new(arithmetic::add, 2, gas::VERYLOW);
+    table[MUL as usize] = OpInfo::new(arithmetic::mul, 2, gas::LOW);
+    table[SUB as usize] = OpInfo::new(arithmetic::sub, 2, gas::VERYLOW);
+    table[DIV as usize] = OpInfo::new(arithmetic::div, 2, gas::LOW);
+    table[SDIV as usize] = OpInfo::new(arithmetic::sdiv, 2, gas::LOW);
+    table[MOD as usize] = OpInfo::new(arithmetic::rem, 2, gas::LOW);
+    table[SMOD as usize] = OpInfo::new(arithmetic::smod, 2, gas::LOW);
+    table[ADDMOD as usize] = OpInfo::new(arithmetic::addmod, 3, gas::MID);
+    table[MULMOD as usize] = OpInfo::new(arithmetic::mulmod, 3, gas::MID);
+    table[EXP as usize] = OpInfo::new(arithmetic::exp, 2, gas::HIGH);
+    table[SIGNEXTEND as usize] = OpInfo::new(arithmetic::signextend, 2, gas::LOW);
+
+    table[LT as usize] = OpInfo::new(bitwise::lt, 2, gas::VERYLOW);
+    table[GT as usize] = OpInfo::new(bitwise::gt, 2, gas::VERYLOW);
+    table[SLT as usize] = OpInfo::new(bitwise::slt, 2, gas::VERYLOW);
+    table[SGT as usize] = OpInfo::new(bitwise::sgt, 2, gas::VERYLOW);
+    table[EQ as usize] = OpInfo::new(bitwise::eq, 2, gas::VERYLOW);
+    table[ISZERO as usize] = OpInfo::new(bitwise::iszero, 1, gas::VERYLOW);
+    table[AND as usize] = OpInfo::new(bitwise::and, 2, gas::VERYLOW);
+    table[OR as usize] = OpInfo::new(bitwise::or, 2, gas::VERYLOW);
+    table[XOR as usize] = OpInfo::new(bitwise::xor, 2, gas::VERYLOW);
+    table[NOT as usize] = OpInfo::new(bitwise::not, 1, gas::VERYLOW);
+    table[BYTE as usize] = OpInfo::new(bitwise::byte, 2, gas::VERYLOW);
+    table[SHL as usize] = OpInfo::new(bitwise::shl, 2, gas::VERYLOW);
+    table[SHR as usize] = OpInfo::new(bitwise::shr, 2, gas::VERYLOW);
+    table[SAR as usize] = OpInfo::new(bitwise::sar, 2, gas::VERYLOW);
+
+    table[KECCAK256 as usize] = OpInfo::new(system::keccak256, 2, gas::KECCAK256);
+
+    table[ADDRESS as usize] = OpInfo::new(environment::address, 0, gas::BASE);
+    table[BALANCE as usize] = OpInfo::new(host::balance, 1, gas::ACCOUNT_ACCESS);
+    table[ORIGIN as usize] = OpInfo::new(environment::origin, 0, gas::BASE);
+    table[CALLER as usize] = OpInfo::new(environment::caller, 0, gas::BASE);
+    table[CALLVALUE as usize] = OpInfo::new(environment::callvalue, 0, gas::BASE);
+    table[CALLDATALOAD as usize] = OpInfo::new(environment::calldataload, 1, gas::VERYLOW);
+    table[CALLDATASIZE as usize] = OpInfo::new(environment::calldatasize, 0, gas::BASE);
+    table[CALLDATACOPY as usize] = OpInfo::new(environment::calldatacopy, 3, gas::VERYLOW);
+    table[CODESIZE as usize] = OpInfo::new(environment::codesize, 0, gas::BASE);
+    table[CODECOPY as usize] = OpInfo::new(environment::codecopy, 3, gas::VERYLOW);
+    table[GASPRICE as usize] = OpInfo::new(environment::gasprice, 0, gas::BASE);
+    table[EXTCODESIZE as usize] = OpInfo::new(host::extcodesize, 1, gas::ACCOUNT_ACCESS);
+    table[EXTCODECOPY as usize] = OpInfo::new(host::extcodecopy, 4, gas::ACCOUNT_ACCESS);
+    table[RETURNDATASIZE as usize] = OpInfo::new(environment::returndatasize, 0, gas::BASE);
+    table[RETURNDATACOPY as usize] = OpInfo::new(environment::returndatacopy, 3, gas::VERYLOW);
+    table[EXTCODEHASH as usize] = OpInfo::new(host::extcodehash, 1, gas::ACCOUNT_ACCESS);
+
+    table[BLOCKHASH as usize] = OpInfo::new(environment::blockhash, 1, gas::BLOCKHASH);
+    table[COINBASE as usize] = OpInfo::new(environment::coinbase, 0, gas::BASE);
+    table[TIMESTAMP as usize] = OpInfo::new(environment::timestamp, 0, gas::BASE);
+    table[NUMBER as usize] = OpInfo::new(environment::number, 0, gas::BASE);
+    table[PREVRANDAO as usize] = OpInfo::new(environment::prevrandao, 0, gas::BASE);
+    table[GASLIMIT as usize] = OpInfo::new(environment::gaslimit, 0, gas::BASE);
+    table[CHAINID as usize] = OpInfo::new(environment::chainid, 0, gas::BASE);
+    table[SELFBALANCE as usize] = OpInfo::new(host::selfbalance, 0, gas::LOW);
+    table[BASEFEE as usize] = OpInfo::new(environment::basefee, 0, gas::BASE);
+
+    table[POP as usize] = OpInfo::new(stack::pop, 1, gas::BASE);
+    table[MLOAD as usize] = OpInfo::new(memory::mload, 1, gas::VERYLOW);
+    table[MSTORE as usize] = OpInfo::new(memory::mstore, 2, gas::VERYLOW);
+    table[MSTORE8 as usize] = OpInfo::new(memory::mstore8, 2, gas::VERYLOW);
+    table[SLOAD as usize] = OpInfo::new(host::sload, 1, gas::SLOAD);
+    table[SSTORE as usize] = OpInfo::new(host::sstore, 2, gas::ZERO);
+    table[JUMP as usize] = OpInfo::new(control::jump, 1, gas::MID);
+    table[JUMPI as usize] = OpInfo::new(control::jumpi, 2, gas::HIGH);
+    table[PC as usize] = OpInfo::new(control::pc, 0, gas::BASE);
+    table[MSIZE as usize] = OpInfo::new(memory::msize, 0, gas::BASE);
+    table[GAS as usize] = OpInfo::new(control::gas, 0, gas::BASE);
+    table[JUMPDEST as usize] = OpInfo::new(control::jumpdest, 0, gas::JUMPDEST);
+    table[MCOPY as usize] = OpInfo::new(memory::mcopy, 3, gas::VERYLOW);
+    table[PUSH0 as usize] = OpInfo::new(stack::push0, 0, gas::BASE);
+
+    let mut op = PUSH1;
+    while op <= PUSH32 {
+        table[op as usize] = OpInfo::new(stack::push, 0, gas::VERYLOW);
+        op += 1;
+    }
+
+    // DUPn needs n items, SWAPn needs n + 1
+    let mut op = DUP1;
+    while op <= DUP16 {
+        table[op as usize] = OpInfo::new(stack::dup, op - DUP1 + 1, gas::VERYLOW);
+        op += 1;
+    }
+
+    let mut op = SWAP1;
+    while op <= SWAP16 {
+        table[op as usize] = OpInfo::new(stack::swap, op - SWAP1 + 2, gas::VERYLOW);
+        op += 1;
+    }
+
+    // LOGn takes the memory range and n topics
+    let mut op = LOG0;
+    while op <= LOG4 {
+        table[op as usize] = OpInfo::new(host::log, op - LOG0 + 2, gas::LOG);
+        op += 1;
+    }
+
+    table[CALL as usize] = OpInfo::new(system::call, 7, gas::ACCOUNT_ACCESS);
+    table[CALLCODE as usize] = OpInfo::new(system::callcode, 7, gas::ACCOUNT_ACCESS);
+    table[RETURN as usize] = OpInfo::new(system::ret, 2, gas::ZERO);
+    table[DELEGATECALL as usize] = OpInfo::new(system::delegatecall, 6, gas::ACCOUNT_ACCESS);
+    table[STATICCALL as usize] = OpInfo::new(system::staticcall, 6, gas::ACCOUNT_ACCESS);
+    table[REVERT as usize] = OpInfo::new(system::revert, 2, gas::ZERO);
+    table[INVALID as usize] = OpInfo::new(control::invalid, 0, gas::ZERO);
+
+    table
+}
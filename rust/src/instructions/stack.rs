@@ -0,0 +1,49 @@
+use primitive_types::U256;
+
+use crate::interpreter::Interpreter;
+use crate::HaltReason;
+use crate::opcode;
+
+// POP
+pub(crate) fn pop(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.pop()?;
+    Ok(())
+}
+
+// PUSH0
+pub(crate) fn push0(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    interp.stack.push(U256::zero())?;
+    Ok(())
+}
+
+// PUSH1 --> PUSH32
+pub(crate) fn push(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let size = (interp.current_opcode() - opcode::PUSH1 + 1) as usize;
+
+    // the immediate is big endian and right aligned in the word, a truncated
+    // one at the end of the code is padded with zeros on the right
+    let mut word = [0u8; 32];
+    interp.bytecode.read(interp.pc, &mut word[32 - size..]);
+    interp.pc += size;
+
+    let value = U256::from_big_endian(&word);
+
+    interp.stack.push(value)?;
+    Ok(())
+}
+
+// DUP1 --> DUP16
+pub(crate) fn dup(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let index = (interp.current_opcode() - opcode::DUP1) as usize;
+
+    let value = interp.stack.peek(index)?;
+    interp.stack.push(value)?;
+    Ok(())
+}
+
+// SWAP1 --> SWAP16
+pub(crate) fn swap(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let index = (interp.current_opcode() - opcode::SWAP1) as usize;
+
+    interp.stack.swap(index + 1)
+}
@@ -0,0 +1,155 @@
+use primitive_types::U256;
+
+use super::bitwise::bool_to_word;
+use crate::env::{word_to_address, CallContext};
+use crate::gas;
+use crate::interpreter::Interpreter;
+use crate::keccak;
+use crate::memory::to_range;
+use crate::HaltReason;
+
+// KECCAK256, also known as SHA3
+pub(crate) fn keccak256(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let offset = interp.stack.pop()?;
+    let len = interp.stack.pop()?;
+
+    let (offset, len) = to_range(offset, len)?;
+
+    interp.gas.record_cost(gas::keccak256_cost(len))?;
+    interp.expand_memory(offset, len)?;
+
+    let hash = keccak::keccak256(interp.memory.slice(offset, len));
+    interp.stack.push(U256::from_big_endian(&hash))?;
+    Ok(())
+}
+
+// copy the memory range that RETURN and REVERT hand back to the caller
+fn set_output(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    let offset = interp.stack.pop()?;
+    let len = interp.stack.pop()?;
+
+    let (offset, len) = to_range(offset, len)?;
+    interp.expand_memory(offset, len)?;
+
+    interp.output = interp.memory.slice(offset, len).to_vec();
+    Ok(())
+}
+
+// RETURN
+pub(crate) fn ret(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    set_output(interp)?;
+    Err(HaltReason::Return)
+}
+
+// REVERT
+pub(crate) fn revert(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    set_output(interp)?;
+    Err(HaltReason::Revert)
+}
+
+// the four ways of running another account's code
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CallKind {
+    Call,
+    // the other account's code against our own storage, sending value to
+    // ourselves
+    CallCode,
+    // like CallCode but keeping our caller and value
+    DelegateCall,
+    // like Call without value, and nothing in the callee may change state
+    StaticCall,
+}
+
+// CALL
+pub(crate) fn call(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    message_call(interp, CallKind::Call)
+}
+
+// CALLCODE
+pub(crate) fn callcode(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    message_call(interp, CallKind::CallCode)
+}
+
+// DELEGATECALL
+pub(crate) fn delegatecall(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    message_call(interp, CallKind::DelegateCall)
+}
+
+// STATICCALL
+pub(crate) fn staticcall(interp: &mut Interpreter) -> Result<(), HaltReason> {
+    message_call(interp, CallKind::StaticCall)
+}
+
+fn message_call(interp: &mut Interpreter, kind: CallKind) -> Result<(), HaltReason> {
+    let gas_limit = interp.stack.pop()?;
+    let to = word_to_address(interp.stack.pop()?);
+    let value = match kind {
+        CallKind::Call | CallKind::CallCode => interp.stack.pop()?,
+        CallKind::DelegateCall | CallKind::StaticCall => U256::zero(),
+    };
+    let in_offset = interp.stack.pop()?;
+    let in_len = interp.stack.pop()?;
+    let out_offset = interp.stack.pop()?;
+    let out_len = interp.stack.pop()?;
+
+    if interp.is_static && kind == CallKind::Call && !value.is_zero() {
+        return Err(HaltReason::StaticViolation);
+    }
+
+    let (in_offset, in_len) = to_range(in_offset, in_len)?;
+    let (out_offset, out_len) = to_range(out_offset, out_len)?;
+
+    interp.expand_memory(in_offset, in_len)?;
+    interp.expand_memory(out_offset, out_len)?;
+
+    if !value.is_zero() {
+        interp.gas.record_cost(gas::CALL_VALUE)?;
+
+        // CALLCODE sends the value to ourselves, which always exists
+        if kind == CallKind::Call && !interp.state.exists(&to) {
+            interp.gas.record_cost(gas::NEW_ACCOUNT)?;
+        }
+    }
+
+    let available = gas::max_call_gas(interp.gas.remaining());
+    let gas_limit = gas_limit.min(U256::from(available)).as_u64();
+    interp.gas.record_cost(gas_limit)?;
+
+    let stipend = if value.is_zero() { 0 } else { gas::CALL_STIPEND };
+
+    let input = interp.memory.slice(in_offset, in_len).to_vec();
+    let context = match kind {
+        CallKind::Call | CallKind::StaticCall => CallContext {
+            address: to,
+            caller: interp.context.address,
+            value,
+            input,
+        },
+        CallKind::CallCode => CallContext {
+            address: interp.context.address,
+            caller: interp.context.address,
+            value,
+            input,
+        },
+        CallKind::DelegateCall => CallContext {
+            input,
+            ..interp.context.clone()
+        },
+    };
+
+    // DELEGATECALL only passes its own value along, nothing is sent
+    let transfer = if kind == CallKind::DelegateCall { U256::zero() } else { value };
+    let is_static = kind == CallKind::StaticCall;
+
+    let outcome = interp.call(to, context, transfer, gas_limit + stipend, is_static);
+
+    interp.gas.return_unused(outcome.gas_left);
+
+    // only as much output as the callee produced is written
+    let len = out_len.min(outcome.output.len());
+    interp.memory.copy_from(&outcome.output, out_offset, len);
+    interp.return_data = outcome.output;
+
+    interp.stack.push(bool_to_word(outcome.success))?;
+    Ok(())
+}
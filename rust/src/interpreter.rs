@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use primitive_types::U256;
+
+use crate::analysis::{AnalyzedBytecode, BytecodeCache};
+use crate::env::{Address, CallContext, Environment};
+use crate::gas::Gas;
+use crate::instructions::TABLE;
+use crate::memory::Memory;
+use crate::stack::Stack;
+use crate::state::WorldState;
+use crate::{HaltReason, Log};
+
+pub(crate) const CALL_DEPTH_LIMIT: usize = 1024;
+
+// how a message call ended, as far as the calling frame cares
+pub(crate) struct CallOutcome {
+    pub(crate) success: bool,
+    pub(crate) output: Vec<u8>,
+    pub(crate) gas_left: u64,
+}
+
+impl CallOutcome {
+    // the call never started, all of its gas goes back to the caller
+    fn rejected(gas_limit: u64) -> Self {
+        CallOutcome {
+            success: false,
+            output: Vec::new(),
+            gas_left: gas_limit,
+        }
+    }
+}
+
+pub(crate) struct Interpreter<'a> {
+    pub(crate) bytecode: Arc<AnalyzedBytecode>,
+    pub(crate) pc: usize,
+    pub(crate) stack: Stack,
+    pub(crate) memory: Memory,
+    pub(crate) gas: Gas,
+    pub(crate) context: CallContext,
+    // 0 for the frame the transaction started
+    pub(crate) depth: usize,
+    pub(crate) env: &'a Environment,
+    pub(crate) state: &'a mut WorldState,
+    // callee code is looked up here instead of being analyzed on every call
+    pub(crate) cache: &'a mut BytecodeCache,
+    pub(crate) logs: Vec<Log>,
+    // the data handed back by RETURN or REVERT
+    pub(crate) output: Vec<u8>,
+    // the output of the last call this frame made
+    pub(crate) return_data: Vec<u8>,
+    // set for read-only execution, where nothing may change the state
+    pub(crate) is_static: bool,
+}
+
+impl<'a> Interpreter<'a> {
+    pub(crate) fn new(
+        bytecode: Arc<AnalyzedBytecode>,
+        context: CallContext,
+        gas: Gas,
+        depth: usize,
+        env: &'a Environment,
+        state: &'a mut WorldState,
+        cache: &'a mut BytecodeCache,
+    ) -> Self {
+        Interpreter {
+            bytecode,
+            pc: 0,
+            stack: Stack::new(),
+            memory: Memory::new(),
+            gas,
+            context,
+            depth,
+            env,
+            state,
+            cache,
+            logs: Vec::new(),
+            output: Vec::new(),
+            return_data: Vec::new(),
+            is_static: false,
+        }
+    }
+
+    // when it returns, the pc points at the opcode that halted
+    pub(crate) fn run(&mut self) -> HaltReason {
+        let reason = self.execute();
+
+        // an exceptional halt burns whatever gas was left
+        if !reason.is_success() && reason != HaltReason::Revert {
+            self.gas.consume_all();
+        }
+
+        reason
+    }
+
+    // fetch, advance the pc past the opcode and dispatch through the table
+    // running off the end of the code behaves like STOP
+    fn execute(&mut self) -> HaltReason {
+        while let Some(&opcode) = self.bytecode.code().get(self.pc) {
+            let start = self.pc;
+            self.pc += 1;
+
+            let info = &TABLE[opcode as usize];
+
+            // underflow is caught here so a handler never runs half way
+            if self.stack.len() < info.inputs as usize {
+                self.pc = start;
+                return HaltReason::StackUnderflow;
+            }
+
+            if let Err(reason) = self.gas.record_cost(info.gas) {
+                self.pc = start;
+                return reason;
+            }
+
+            if let Err(reason) = (info.execute)(self) {
+                self.pc = start;
+                return reason;
+            }
+        }
+
+        // a truncated PUSH at the end can leave the pc past the code
+        self.pc = self.bytecode.code().len();
+        HaltReason::Stop
+    }
+
+    // run the code at `code_address` in a new frame, sending `transfer`
+    // from the caller to the address of the context first, a failed frame
+    // leaves the state exactly as it found it
+    // frames recurse on the native stack, `execute` in lib.rs makes sure
+    // there is enough of it for the full 1024
+    pub(crate) fn call(
+        &mut self,
+        code_address: Address,
+        context: CallContext,
+        transfer: U256,
+        gas_limit: u64,
+        is_static: bool,
+    ) -> CallOutcome {
+        if self.depth == CALL_DEPTH_LIMIT {
+            return CallOutcome::rejected(gas_limit);
+        }
+
+        let checkpoint = self.state.checkpoint();
+
+        if !self.state.transfer(context.caller, context.address, transfer) {
+            return CallOutcome::rejected(gas_limit);
+        }
+
+        let bytecode = match self.state.get(&code_address) {
+            Some(account) => self.cache.get_or_analyze(account.code_hash(), account.code()),
+            None => Arc::new(AnalyzedBytecode::new(&[])),
+        };
+        // an unmetered frame only ever starts unmetered frames
+        let gas = if self.gas.is_metered() {
+            Gas::new(gas_limit)
+        } else {
+            Gas::unmetered()
+        };
+
+        let mut child = Interpreter::new(
+            bytecode,
+            context,
+            gas,
+            self.depth + 1,
+            self.env,
+            self.state,
+            self.cache,
+        );
+        // once static, every frame below stays static
+        child.is_static = self.is_static || is_static;
+
+        let reason = child.run();
+        let gas_left = child.gas.remaining();
+        let output = child.output;
+
+        if reason.is_success() {
+            self.logs.extend(child.logs);
+        } else {
+            self.state.revert_to(checkpoint);
+        }
+
+        CallOutcome {
+            success: reason.is_success(),
+            output,
+            gas_left,
+        }
+    }
+
+    // charge for growing memory to cover the range, and only grow once the
+    // gas is paid
+    pub(crate) fn expand_memory(&mut self, offset: usize, len: usize) -> Result<(), HaltReason> {
+        let cost = self.memory.expansion_cost(offset, len)?;
+        self.gas.record_cost(cost)?;
+
+        self.memory.resize_for(offset, len);
+        Ok(())
+    }
+
+    // the opcode being executed, the pc has already moved past it
+    pub(crate) fn current_opcode(&self) -> u8 {
+        self.bytecode.code()[self.pc - 1]
+    }
+
+    pub(crate) fn check_valid_jump_location(&self, location: U256) -> bool {
+        location < U256::from(self.bytecode.code().len())
+            && self.bytecode.is_jumpdest(location.as_usize())
+    }
+}
@@ -1,4 +1,4 @@
-/**
+/*
  * EVM From Scratch
  * Rust template
  *
@@ -13,7 +13,9 @@
  * to Rust, implement EVM in another programming language first.
  */
 
-use evm::evm;
+use std::collections::HashMap;
+
+use evm::{evm_unmetered, Account, Address, Block, Environment, Log, Transaction, WorldState};
 use primitive_types::U256;
 use serde::Deserialize;
 
@@ -22,23 +24,136 @@ struct Evmtest {
     name: String,
     hint: String,
     code: Code,
+    #[serde(default)]
+    tx: Tx,
+    #[serde(default)]
+    block: BlockInfo,
+    #[serde(default)]
+    state: HashMap<String, AccountInfo>,
     expect: Expect,
 }
 
+#[derive(Debug, Deserialize)]
+struct AccountInfo {
+    balance: Option<String>,
+    code: Option<Code>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Tx {
+    to: Option<String>,
+    from: Option<String>,
+    origin: Option<String>,
+    gasprice: Option<String>,
+    value: Option<String>,
+    data: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BlockInfo {
+    coinbase: Option<String>,
+    timestamp: Option<String>,
+    number: Option<String>,
+    difficulty: Option<String>,
+    gaslimit: Option<String>,
+    chainid: Option<String>,
+    basefee: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct Code {
-    asm: String,
+    asm: Option<String>,
     bin: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct LogInfo {
+    address: String,
+    data: String,
+    topics: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct Expect {
     stack: Option<Vec<String>>,
     success: bool,
-    // #[serde(rename = "return")]
-    // ret: Option<String>,
+    logs: Option<Vec<LogInfo>>,
+    #[serde(rename = "return")]
+    ret: Option<String>,
+}
+
+fn parse_u256(value: &Option<String>) -> U256 {
+    value
+        .as_ref()
+        .map(|v| U256::from_str_radix(v, 16).unwrap())
+        .unwrap_or_default()
+}
+
+fn parse_bytes(value: &Option<String>) -> Vec<u8> {
+    value
+        .as_ref()
+        .map(|v| hex::decode(v.trim_start_matches("0x")).unwrap())
+        .unwrap_or_default()
+}
+
+// some tests use short addresses like 0x1337, so left pad them to 20 bytes
+fn parse_address(value: &Option<String>) -> Address {
+    value
+        .as_ref()
+        .map(|v| format!("{:0>40}", v.trim_start_matches("0x")))
+        .map(|v| Address::from_slice(&hex::decode(v).unwrap()))
+        .unwrap_or_default()
+}
+
+fn parse_log(info: &LogInfo) -> Log {
+    Log {
+        address: parse_address(&Some(info.address.clone())),
+        data: parse_bytes(&Some(info.data.clone())),
+        topics: info.topics.iter().map(|t| parse_u256(&Some(t.clone()))).collect(),
+    }
+}
+
+fn environment(test: &Evmtest) -> Environment {
+    Environment {
+        tx: Transaction {
+            to: parse_address(&test.tx.to),
+            from: parse_address(&test.tx.from),
+            origin: parse_address(&test.tx.origin),
+            gasprice: parse_u256(&test.tx.gasprice),
+            value: parse_u256(&test.tx.value),
+            data: parse_bytes(&test.tx.data),
+        },
+        block: Block {
+            coinbase: parse_address(&test.block.coinbase),
+            timestamp: parse_u256(&test.block.timestamp),
+            number: parse_u256(&test.block.number),
+            difficulty: parse_u256(&test.block.difficulty),
+            gaslimit: parse_u256(&test.block.gaslimit),
+            chainid: parse_u256(&test.block.chainid),
+            basefee: parse_u256(&test.block.basefee),
+            // evm.json carries no block history
+            hashes: HashMap::new(),
+        },
+    }
 }
 
+fn world_state(test: &Evmtest) -> WorldState {
+    let mut state = WorldState::new();
+
+    for (address, info) in &test.state {
+        let code = info
+            .code
+            .as_ref()
+            .map(|code| hex::decode(&code.bin).unwrap())
+            .unwrap_or_default();
+
+        let mut account = Account::with_code(code);
+        account.balance = parse_u256(&info.balance);
+        state.insert(parse_address(&Some(address.clone())), account);
+    }
+
+    state
+}
 
 fn main() {
     let text = std::fs::read_to_string("../evm.json").unwrap();
@@ -51,7 +166,12 @@ fn main() {
 
         let code: Vec<u8> = hex::decode(&test.code.bin).unwrap();
 
-        let result = evm(&code);
+        let env = environment(test);
+        let mut state = world_state(test);
+
+        // evm.json was written before gas was metered: it expects GAS to
+        // push MAX_UINT256 and its calls forward no gas, so it runs unmetered
+        let result = evm_unmetered(&code, &env, &mut state);
 
         let mut expected_stack: Vec<U256> = Vec::new();
         if let Some(ref stacks) = test.expect.stack {
@@ -60,20 +180,19 @@ fn main() {
             }
         }
 
-        let mut matching = result.stack.len() == expected_stack.len();
-        if matching {
-            for i in 0..result.stack.len() {
-                if result.stack[i] != expected_stack[i] {
-                    matching = false;
-                    break;
-                }
-            }
-        }
-        
-        matching = matching && result.success == test.expect.success;
+        let expected_logs: Option<Vec<Log>> =
+            test.expect.logs.as_ref().map(|logs| logs.iter().map(parse_log).collect());
+
+        let expected_return: Option<Vec<u8>> =
+            test.expect.ret.as_ref().map(|ret| parse_bytes(&Some(ret.clone())));
+
+        let matching = result.stack == expected_stack
+            && result.success == test.expect.success
+            && expected_logs.as_ref().is_none_or(|logs| *logs == result.logs)
+            && expected_return.as_ref().is_none_or(|ret| *ret == result.return_data);
 
         if !matching {
-            println!("Instructions: \n{}\n", test.code.asm);
+            println!("Instructions: \n{}\n", test.code.asm.as_deref().unwrap_or_default());
 
             println!("Expected success: {:?}", test.expect.success);
             println!("Expected stack: [");
@@ -81,13 +200,22 @@ fn main() {
                 println!("  {:#X},", v);
             }
             println!("]\n");
-            
+            if let Some(ref logs) = expected_logs {
+                println!("Expected logs: {:#?}\n", logs);
+            }
+            if let Some(ref ret) = test.expect.ret {
+                println!("Expected return: {}\n", ret);
+            }
+
             println!("Actual success: {:?}", result.success);
+            println!("Halted with: {:?} at pc {}", result.reason, result.pc);
             println!("Actual stack: [");
             for v in result.stack {
                 println!("  {:#X},", v);
             }
             println!("]\n");
+            println!("Actual logs: {:#?}\n", result.logs);
+            println!("Actual return: {}\n", hex::encode(&result.return_data));
 
             println!("\nHint: {}\n", test.hint);
             println!("Progress: {}/{}\n\n", index, total);
@@ -0,0 +1,137 @@
+use primitive_types::U256;
+
+use crate::HaltReason;
+
+// hard cap on memory size, well past anything a real gas limit can pay for,
+// so a huge offset fails the call instead of trying to allocate it
+pub(crate) const MEMORY_LIMIT: usize = 1 << 25;
+
+// byte addressed, grows in 32 byte words and reads zero where never written
+pub(crate) struct Memory {
+    data: Vec<u8>,
+}
+
+impl Memory {
+    pub(crate) fn new() -> Self {
+        Memory { data: Vec::new() }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    // the gas cost of growing to cover `offset..offset + len`, rounded up
+    // to a whole word, without growing yet
+    // a zero length access never expands memory, whatever the offset
+    pub(crate) fn expansion_cost(&self, offset: usize, len: usize) -> Result<u64, HaltReason> {
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let end = offset.checked_add(len).ok_or(HaltReason::MemoryOverflow)?;
+        if end > MEMORY_LIMIT {
+            return Err(HaltReason::MemoryOverflow);
+        }
+
+        let new_size = end.div_ceil(32) * 32;
+        if new_size <= self.data.len() {
+            return Ok(0);
+        }
+
+        Ok(total_cost(new_size / 32) - total_cost(self.data.len() / 32))
+    }
+
+    // grow to cover a range `expansion_cost` has already accepted
+    pub(crate) fn resize_for(&mut self, offset: usize, len: usize) {
+        if len == 0 {
+            return;
+        }
+
+        let new_size = (offset + len).div_ceil(32) * 32;
+        if new_size > self.data.len() {
+            self.data.resize(new_size, 0);
+        }
+    }
+
+    pub(crate) fn load_word(&self, offset: usize) -> U256 {
+        U256::from_big_endian(&self.data[offset..offset + 32])
+    }
+
+    pub(crate) fn store_word(&mut self, offset: usize, value: U256) {
+        value.to_big_endian(&mut self.data[offset..offset + 32]);
+    }
+
+    pub(crate) fn store_byte(&mut self, offset: usize, value: u8) {
+        self.data[offset] = value;
+    }
+
+    // write `len` bytes at `offset` from `source`, zero filling whatever
+    // the source is too short to cover
+    pub(crate) fn copy_from(&mut self, source: &[u8], offset: usize, len: usize) {
+        let available = source.len().min(len);
+
+        self.data[offset..offset + available].copy_from_slice(&source[..available]);
+        self.data[offset + available..offset + len].fill(0);
+    }
+
+    // copy within memory, the two ranges may overlap
+    pub(crate) fn copy_within(&mut self, source: usize, offset: usize, len: usize) {
+        self.data.copy_within(source..source + len, offset);
+    }
+
+    pub(crate) fn slice(&self, offset: usize, len: usize) -> &[u8] {
+        &self.data[offset..offset + len]
+    }
+}
+
+// total cost of a memory of `words` words, 3 per word plus the quadratic part
+fn total_cost(words: usize) -> u64 {
+    let words = words as u64;
+    3 * words + words * words / 512
+}
+
+// stack values used as memory offsets or sizes
+pub(crate) fn to_usize(value: U256) -> Result<usize, HaltReason> {
+    if value > U256::from(usize::MAX) {
+        return Err(HaltReason::MemoryOverflow);
+    }
+
+    Ok(value.as_usize())
+}
+
+// an offset and length pair, where the offset is ignored when the length is
+// zero since nothing is touched
+pub(crate) fn to_range(offset: U256, len: U256) -> Result<(usize, usize), HaltReason> {
+    let len = to_usize(len)?;
+    if len == 0 {
+        return Ok((0, 0));
+    }
+
+    Ok((to_usize(offset)?, len))
+}
+
+// stack values used as offsets into code or calldata, where anything past
+// the end reads as zero anyway
+pub(crate) fn to_usize_saturating(value: U256) -> usize {
+    if value > U256::from(usize::MAX) {
+        usize::MAX
+    } else {
+        value.as_usize()
+    }
+}
+
+// code and calldata are treated as if they were followed by an infinite run
+// of zero bytes
+pub(crate) fn read_padded(source: &[u8], offset: usize, out: &mut [u8]) {
+    let available = source.len().saturating_sub(offset).min(out.len());
+
+    if available > 0 {
+        out[..available].copy_from_slice(&source[offset..offset + available]);
+    }
+    out[available..].fill(0);
+}
+
+// the part of `source` from `offset` on, empty when the offset is past the end
+pub(crate) fn tail(source: &[u8], offset: usize) -> &[u8] {
+    &source[offset.min(source.len())..]
+}
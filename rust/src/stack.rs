@@ -0,0 +1,61 @@
+use primitive_types::U256;
+
+use crate::HaltReason;
+
+pub(crate) const STACK_LIMIT: usize = 1024;
+
+// the top of the stack is the last element, so push and pop never have to
+// shift the rest of the items around
+pub(crate) struct Stack {
+    data: Vec<U256>,
+}
+
+impl Stack {
+    pub(crate) fn new() -> Self {
+        Stack {
+            data: Vec::with_capacity(STACK_LIMIT),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub(crate) fn push(&mut self, value: U256) -> Result<(), HaltReason> {
+        if self.data.len() == STACK_LIMIT {
+            return Err(HaltReason::StackOverflow);
+        }
+
+        self.data.push(value);
+        Ok(())
+    }
+
+    pub(crate) fn pop(&mut self) -> Result<U256, HaltReason> {
+        self.data.pop().ok_or(HaltReason::StackUnderflow)
+    }
+
+    // item `index` places below the top, 0 is the top itself
+    pub(crate) fn peek(&self, index: usize) -> Result<U256, HaltReason> {
+        if index >= self.data.len() {
+            return Err(HaltReason::StackUnderflow);
+        }
+
+        Ok(self.data[self.data.len() - 1 - index])
+    }
+
+    // swap the top with the item `index` places below it
+    pub(crate) fn swap(&mut self, index: usize) -> Result<(), HaltReason> {
+        if index >= self.data.len() {
+            return Err(HaltReason::StackUnderflow);
+        }
+
+        let top = self.data.len() - 1;
+        self.data.swap(top, top - index);
+        Ok(())
+    }
+
+    // the items in top-first order, the way results are reported
+    pub(crate) fn to_vec(&self) -> Vec<U256> {
+        self.data.iter().rev().copied().collect()
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use primitive_types::U256;
+
+use crate::env::Address;
+use crate::keccak::keccak256;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    pub nonce: u64,
+    pub balance: U256,
+    // only set through `with_code` and `set_code`, which keep the hash in step
+    code: Vec<u8>,
+    code_hash: [u8; 32],
+    pub storage: HashMap<U256, U256>,
+}
+
+impl Account {
+    pub fn with_code(code: Vec<u8>) -> Self {
+        Account {
+            nonce: 0,
+            balance: U256::zero(),
+            code_hash: keccak256(&code),
+            code,
+            storage: HashMap::new(),
+        }
+    }
+
+    pub fn code(&self) -> &[u8] {
+        &self.code
+    }
+
+    pub fn set_code(&mut self, code: Vec<u8>) {
+        self.code_hash = keccak256(&code);
+        self.code = code;
+    }
+
+    // hashed once when the code was set, not on every lookup
+    pub fn code_hash(&self) -> [u8; 32] {
+        self.code_hash
+    }
+}
+
+// no code, which still hashes to keccak256 of the empty string
+impl Default for Account {
+    fn default() -> Self {
+        Account::with_code(Vec::new())
+    }
+}
+
+// what a write during execution replaced, so it can be put back
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Change {
+    AccountCreated(Address),
+    Balance { address: Address, previous: U256 },
+    Storage { address: Address, key: U256, previous: U256 },
+}
+
+// every account the code can see, anything missing does not exist
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorldState {
+    accounts: HashMap<Address, Account>,
+    // writes made by the running execution, newest last, empty otherwise
+    journal: Vec<Change>,
+}
+
+impl WorldState {
+    pub fn new() -> Self {
+        WorldState::default()
+    }
+
+    pub fn insert(&mut self, address: Address, account: Account) {
+        self.accounts.insert(address, account);
+    }
+
+    pub fn get(&self, address: &Address) -> Option<&Account> {
+        self.accounts.get(address)
+    }
+
+    pub fn get_mut(&mut self, address: &Address) -> Option<&mut Account> {
+        self.accounts.get_mut(address)
+    }
+
+    pub fn exists(&self, address: &Address) -> bool {
+        self.accounts.contains_key(address)
+    }
+
+    pub fn balance(&self, address: &Address) -> U256 {
+        self.get(address).map(|account| account.balance).unwrap_or_default()
+    }
+
+    // move value between accounts, false when the sender cannot afford it
+    // or the recipient's balance would overflow
+    pub(crate) fn transfer(&mut self, from: Address, to: Address, value: U256) -> bool {
+        if value.is_zero() {
+            return true;
+        }
+
+        if self.balance(&from) < value {
+            return false;
+        }
+
+        if from != to && self.balance(&to).checked_add(value).is_none() {
+            return false;
+        }
+
+        self.set_balance(from, self.balance(&from) - value);
+        self.set_balance(to, self.balance(&to) + value);
+        true
+    }
+
+    fn set_balance(&mut self, address: Address, balance: U256) {
+        let previous = self.balance(&address);
+        self.journal.push(Change::Balance { address, previous });
+
+        self.account_mut(address).balance = balance;
+    }
+
+    // zero for slots that were never written
+    pub fn storage(&self, address: &Address, key: U256) -> U256 {
+        self.get(address)
+            .and_then(|account| account.storage.get(&key).copied())
+            .unwrap_or_default()
+    }
+
+    // writing zero deletes the slot, writing to a missing account creates it
+    pub(crate) fn set_storage(&mut self, address: Address, key: U256, value: U256) {
+        let previous = self.storage(&address, key);
+        if previous == value {
+            return;
+        }
+
+        self.journal.push(Change::Storage { address, key, previous });
+        write_slot(&mut self.account_mut(address).storage, key, value);
+    }
+
+    // the account to write to, created and journaled if it is missing
+    fn account_mut(&mut self, address: Address) -> &mut Account {
+        if !self.exists(&address) {
+            self.journal.push(Change::AccountCreated(address));
+        }
+
+        self.accounts.entry(address).or_default()
+    }
+
+    // a point the writes made after it can be undone back to
+    pub(crate) fn checkpoint(&self) -> usize {
+        self.journal.len()
+    }
+
+    // undo every write made since the checkpoint, newest first
+    pub(crate) fn revert_to(&mut self, checkpoint: usize) {
+        while self.journal.len() > checkpoint {
+            match self.journal.pop() {
+                Some(Change::AccountCreated(address)) => {
+                    self.accounts.remove(&address);
+                }
+                Some(Change::Balance { address, previous }) => {
+                    if let Some(account) = self.accounts.get_mut(&address) {
+                        account.balance = previous;
+                    }
+                }
+                Some(Change::Storage { address, key, previous }) => {
+                    if let Some(account) = self.accounts.get_mut(&address) {
+                        write_slot(&mut account.storage, key, previous);
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+
+    // keep every write, once the outermost execution is done
+    pub(crate) fn commit(&mut self) {
+        self.journal.clear();
+    }
+
+    // empty for accounts that do not exist
+    pub fn code(&self, address: &Address) -> &[u8] {
+        self.get(address).map(Account::code).unwrap_or_default()
+    }
+}
+
+fn write_slot(storage: &mut HashMap<U256, U256>, key: U256, value: U256) {
+    if value.is_zero() {
+        storage.remove(&key);
+    } else {
+        storage.insert(key, value);
+    }
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use std::sync::Arc;
+
+use evm::opcode::*;
+use evm::{
+    evm_analyzed, keccak256, Account, Address, AnalyzedBytecode, BytecodeCache, Environment,
+    HaltReason, WorldState,
+};
+use primitive_types::U256;
+
+use common::{execute, GAS_LIMIT};
+
+#[test]
+fn jumpdest_is_marked() {
+    let bytecode = AnalyzedBytecode::new(&[JUMPDEST, 0x00, JUMPDEST]);
+
+    assert!(bytecode.is_jumpdest(0));
+    assert!(!bytecode.is_jumpdest(1));
+    assert!(bytecode.is_jumpdest(2));
+    assert!(!bytecode.is_jumpdest(3));
+}
+
+#[test]
+fn jumpdest_inside_push_data_is_not_marked() {
+    // PUSH1 0x5b, JUMPDEST
+    let bytecode = AnalyzedBytecode::new(&[PUSH1, JUMPDEST, JUMPDEST]);
+    assert!(!bytecode.is_jumpdest(1));
+    assert!(bytecode.is_jumpdest(2));
+
+    // a 0x5b at every position of a PUSH32 immediate
+    let mut code = vec![PUSH32];
+    code.extend([JUMPDEST; 32]);
+    code.push(JUMPDEST);
+
+    let bytecode = AnalyzedBytecode::new(&code);
+    for pc in 0..33 {
+        assert!(!bytecode.is_jumpdest(pc), "pc {}", pc);
+    }
+    assert!(bytecode.is_jumpdest(33));
+}
+
+#[test]
+fn jumpdest_in_truncated_push_is_not_marked() {
+    // PUSH2 with only one immediate byte, which is 0x5b
+    let bytecode = AnalyzedBytecode::new(&[PUSH1 + 1, JUMPDEST]);
+    assert!(!bytecode.is_jumpdest(1));
+
+    // the walk still marks a JUMPDEST that follows a shorter, complete PUSH
+    let bytecode = AnalyzedBytecode::new(&[PUSH1, 0x00, JUMPDEST, PUSH1 + 1, JUMPDEST]);
+    assert!(bytecode.is_jumpdest(2));
+    assert!(!bytecode.is_jumpdest(4));
+}
+
+#[test]
+fn jump_into_push_data_fails() {
+    // PUSH1 4, JUMP, PUSH1 0x5b
+    let result = execute(&[PUSH1, 0x04, JUMP, PUSH1, JUMPDEST]);
+
+    assert!(!result.success);
+    assert_eq!(result.reason, HaltReason::InvalidJump(U256::from(4)));
+    assert_eq!(result.gas_used, GAS_LIMIT);
+}
+
+#[test]
+fn cache_hit_returns_the_same_analysis() {
+    let mut cache = BytecodeCache::new();
+    let code = [PUSH1, 0x01, JUMPDEST];
+
+    let hash = keccak256(&code);
+
+    let first = cache.get_or_analyze(hash, &code);
+    let second = cache.get_or_analyze(hash, &code);
+
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(cache.len(), 1);
+    assert!(Arc::ptr_eq(&cache.get(&hash).unwrap(), &first));
+
+    let other = cache.get_or_analyze(keccak256(&[JUMPDEST]), &[JUMPDEST]);
+    assert!(!Arc::ptr_eq(&first, &other));
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn called_code_goes_through_the_cache() {
+    let callee = Address::from_low_u64_be(0xc42);
+    let callee_code = vec![PUSH1, 0x01, 0x00];
+
+    let mut state = WorldState::new();
+    state.insert(callee, Account::with_code(callee_code.clone()));
+
+    // CALL the callee twice with all the gas
+    let mut code = Vec::new();
+    for _ in 0..2 {
+        // PUSH0 x5, PUSH20 callee, GAS, CALL
+        code.extend([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, PUSH1 + 19]);
+        code.extend(callee.as_bytes());
+        code.extend([GAS, CALL]);
+    }
+
+    let mut cache = BytecodeCache::new();
+    let bytecode = cache.get_or_analyze(keccak256(&code), &code);
+    let env = Environment::default();
+    let result = evm_analyzed(bytecode, GAS_LIMIT, &env, &mut state, &mut cache);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::one(), U256::one()]);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&keccak256(&callee_code)).is_some());
+}
+
+#[test]
+fn account_keeps_its_code_hash_in_step() {
+    let mut account = Account::default();
+    assert_eq!(account.code_hash(), keccak256(&[]));
+
+    account.set_code(vec![PUSH1, 0x01]);
+    assert_eq!(account.code(), &[PUSH1, 0x01]);
+    assert_eq!(account.code_hash(), keccak256(&[PUSH1, 0x01]));
+
+    let account = Account::with_code(vec![JUMPDEST]);
+    assert_eq!(account.code_hash(), keccak256(&[JUMPDEST]));
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use evm::opcode::*;
+use evm::{evm, Block, Environment, WorldState};
+use primitive_types::U256;
+
+use common::{push, GAS_LIMIT};
+
+fn blockhash(block: &Block, number: u64) -> U256 {
+    let env = Environment {
+        block: block.clone(),
+        ..Environment::default()
+    };
+
+    let mut code = push(U256::from(number));
+    code.push(BLOCKHASH);
+
+    let result = evm(code, GAS_LIMIT, &env, &mut WorldState::new());
+    assert!(result.success);
+    result.stack[0]
+}
+
+// a made up hash that tells blocks apart
+fn hash(number: u64) -> [u8; 32] {
+    let mut hash = [0xaa; 32];
+    hash[24..].copy_from_slice(&number.to_be_bytes());
+    hash
+}
+
+fn block_with_history() -> Block {
+    let mut block = Block {
+        number: U256::from(300),
+        ..Block::default()
+    };
+    for number in [43u64, 44, 299, 300] {
+        block.hashes.insert(U256::from(number), hash(number));
+    }
+    block
+}
+
+#[test]
+fn recent_block_hash_is_known() {
+    let block = block_with_history();
+
+    // the one before, and the oldest one still in reach
+    assert_eq!(blockhash(&block, 299), U256::from_big_endian(&hash(299)));
+    assert_eq!(blockhash(&block, 44), U256::from_big_endian(&hash(44)));
+}
+
+#[test]
+fn old_current_and_future_blocks_are_zero() {
+    let block = block_with_history();
+
+    // 43 is 257 blocks back
+    assert_eq!(blockhash(&block, 43), U256::zero());
+    assert_eq!(blockhash(&block, 300), U256::zero());
+    assert_eq!(blockhash(&block, 301), U256::zero());
+}
+
+#[test]
+fn missing_history_is_zero() {
+    assert_eq!(blockhash(&block_with_history(), 298), U256::zero());
+    assert_eq!(blockhash(&Block::default(), 0), U256::zero());
+}
@@ -0,0 +1,285 @@
+mod common;
+
+use evm::{evm, Environment, EvmResult, HaltReason, Transaction, WorldState};
+use primitive_types::U256;
+
+use common::{address, contract, CALLEE, GAS_LIMIT};
+
+// the evm.json CALL cases forward no gas, these forward all of it with GAS
+const CALLER: &str = "1000000000000000000000000000000000000aaa";
+
+fn execute(code: &str, state: &mut WorldState) -> EvmResult {
+    let env = Environment {
+        tx: Transaction {
+            to: address(CALLER),
+            ..Transaction::default()
+        },
+        ..Environment::default()
+    };
+
+    evm(hex::decode(code).unwrap(), GAS_LIMIT, &env, state)
+}
+
+// CALL the callee with `value`, copy one byte of output to memory 31 and
+// load the word at 0
+fn call_code(value: u8) -> String {
+    format!("6001601f6000600060{:02x}73{}5af1600051", value, CALLEE)
+}
+
+#[test]
+fn call_returns_output() {
+    let mut state = WorldState::new();
+    // MSTORE 0x42 at 0, RETURN the last byte of the word
+    state.insert(address(CALLEE), contract("60426000526001601ff3", 0));
+
+    let result = execute(&call_code(0), &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::from(0x42), U256::one()]);
+}
+
+#[test]
+fn call_sees_caller_address() {
+    let mut state = WorldState::new();
+    // return CALLER as a word
+    state.insert(address(CALLEE), contract("3360005260206000f3", 0));
+
+    let code = format!("60206000600060006000{}{}5af1600051", "73", CALLEE);
+    let result = execute(&code, &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::from_str_radix(CALLER, 16).unwrap(), U256::one()]);
+}
+
+#[test]
+fn call_keeps_revert_output() {
+    let mut state = WorldState::new();
+    // same as above but REVERT
+    state.insert(address(CALLEE), contract("60426000526001601ffd", 0));
+
+    let result = execute(&call_code(0), &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::from(0x42), U256::zero()]);
+}
+
+#[test]
+fn call_transfers_value() {
+    let mut state = WorldState::new();
+    state.insert(address(CALLER), contract("", 10));
+    state.insert(address(CALLEE), contract("60426000526001601ff3", 0));
+
+    let result = execute(&call_code(7), &mut state);
+
+    assert!(result.success);
+    assert_eq!(state.balance(&address(CALLER)), U256::from(3));
+    assert_eq!(state.balance(&address(CALLEE)), U256::from(7));
+}
+
+#[test]
+fn failed_call_rolls_back_value_and_storage() {
+    let mut state = WorldState::new();
+    state.insert(address(CALLER), contract("", 10));
+    // SSTORE 1 at slot 0, then REVERT
+    state.insert(address(CALLEE), contract("600160005560006000fd", 0));
+
+    let result = execute(&call_code(7), &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack[1], U256::zero());
+    assert_eq!(state.balance(&address(CALLER)), U256::from(10));
+    assert_eq!(state.get(&address(CALLEE)).unwrap().storage.len(), 0);
+}
+
+#[test]
+fn revert_undoes_writes_of_calls_that_succeeded() {
+    let fresh = "00000000000000000000000000000000000000ee";
+
+    let mut state = WorldState::new();
+    state.insert(address(CALLER), contract("", 10));
+    // SSTORE 1 at slot 0
+    state.insert(address(CALLEE), contract("6001600055", 0));
+
+    // CALL the callee, send 3 to an account that does not exist, REVERT
+    let code = format!("{}5f5f5f5f600373{}5af15f5ffd", call_only(), fresh);
+    let result = execute(&code, &mut state);
+
+    assert_eq!(result.reason, HaltReason::Revert);
+    assert_eq!(state.storage(&address(CALLEE), U256::zero()), U256::zero());
+    assert_eq!(state.balance(&address(CALLER)), U256::from(10));
+    assert!(!state.exists(&address(fresh)));
+}
+
+#[test]
+fn call_without_enough_balance_fails() {
+    let mut state = WorldState::new();
+    state.insert(address(CALLEE), contract("60426000526001601ff3", 0));
+
+    let result = execute(&call_code(7), &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::zero(), U256::zero()]);
+}
+
+#[test]
+fn call_that_would_overflow_the_recipient_balance_fails() {
+    let mut state = WorldState::new();
+    state.insert(address(CALLER), contract("", 10));
+    let mut callee = contract("60426000526001601ff3", 0);
+    callee.balance = U256::MAX;
+    state.insert(address(CALLEE), callee);
+
+    let result = execute(&call_code(1), &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::zero(), U256::zero()]);
+    assert_eq!(state.balance(&address(CALLER)), U256::from(10));
+    assert_eq!(state.balance(&address(CALLEE)), U256::MAX);
+}
+
+#[test]
+fn call_depth_is_limited() {
+    let mut state = WorldState::new();
+    // call itself with all the gas it can, then store one more than the
+    // counter its callee left in slot 0, the call at the limit is rejected
+    // PUSH0 x5, ADDRESS, GAS, CALL, POP, PUSH0, SLOAD, PUSH1 1, ADD, PUSH0, SSTORE
+    state.insert(address(CALLEE), contract("5f5f5f5f5f305af1505f546001015f55", 0));
+
+    // enough gas to survive losing a 64th at every level
+    let env = Environment::default();
+    let code = hex::decode(format!("5f5f5f5f5f73{}5af1", CALLEE)).unwrap();
+    let result = evm(code, 1 << 50, &env, &mut state);
+
+    assert!(result.success);
+    assert_eq!(state.storage(&address(CALLEE), U256::zero()), U256::from(1024));
+}
+
+#[test]
+fn staticcall_returns_output() {
+    let mut state = WorldState::new();
+    state.insert(address(CALLEE), contract("60426000526001601ff3", 0));
+
+    let code = format!("6001601f6000600073{}5afa600051", CALLEE);
+    let result = execute(&code, &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::from(0x42), U256::one()]);
+}
+
+#[test]
+fn staticcall_forbids_writes() {
+    // SSTORE, LOG0, and CALL sending value
+    let callees = ["6042600055", "60006000a0", "5f5f5f5f60015f5af1"];
+
+    for callee in callees {
+        let mut state = WorldState::new();
+        state.insert(address(CALLEE), contract(callee, 1));
+
+        let code = format!("5f5f5f5f73{}5afa", CALLEE);
+        let result = execute(&code, &mut state);
+
+        assert!(result.success);
+        assert_eq!(result.stack, vec![U256::zero()], "callee {}", callee);
+    }
+}
+
+#[test]
+fn delegatecall_keeps_storage_caller_and_value() {
+    let mut state = WorldState::new();
+    // store ADDRESS, CALLER and CALLVALUE in slots 0, 1 and 2
+    state.insert(address(CALLEE), contract("305f55336001553460025500", 0));
+
+    let env = Environment {
+        tx: Transaction {
+            to: address(CALLER),
+            from: address("00000000000000000000000000000000000000ff"),
+            value: U256::from(5),
+            ..Transaction::default()
+        },
+        ..Environment::default()
+    };
+    let code = hex::decode(format!("5f5f5f5f73{}5af4", CALLEE)).unwrap();
+    let result = evm(code, GAS_LIMIT, &env, &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::one()]);
+    assert_eq!(result.storage[&U256::zero()], U256::from_str_radix(CALLER, 16).unwrap());
+    assert_eq!(result.storage[&U256::one()], U256::from(0xff));
+    assert_eq!(result.storage[&U256::from(2)], U256::from(5));
+    assert!(state.get(&address(CALLEE)).unwrap().storage.is_empty());
+}
+
+#[test]
+fn callcode_runs_against_own_storage() {
+    let mut state = WorldState::new();
+    state.insert(address(CALLER), contract("", 10));
+    // store CALLER and CALLVALUE in slots 0 and 1
+    state.insert(address(CALLEE), contract("335f553460015500", 0));
+
+    let code = format!("5f5f5f5f600773{}5af2", CALLEE);
+    let result = execute(&code, &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::one()]);
+    assert_eq!(result.storage[&U256::zero()], U256::from_str_radix(CALLER, 16).unwrap());
+    assert_eq!(result.storage[&U256::one()], U256::from(7));
+    assert_eq!(state.balance(&address(CALLER)), U256::from(10));
+}
+
+// CALL the callee with all gas and no output region, leaving the flag
+fn call_only() -> String {
+    format!("5f5f5f5f5f73{}5af1", CALLEE)
+}
+
+#[test]
+fn returndatasize_follows_last_call() {
+    let mut state = WorldState::new();
+    state.insert(address(CALLEE), contract("60426000526001601ff3", 0));
+
+    // RETURNDATASIZE before the call, then after it
+    let code = format!("3d{}3d", call_only());
+    let result = execute(&code, &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::one(), U256::one(), U256::zero()]);
+}
+
+#[test]
+fn returndatacopy_copies_output() {
+    let mut state = WorldState::new();
+    state.insert(address(CALLEE), contract("60426000526001601ff3", 0));
+
+    // RETURNDATACOPY the one byte to memory 0xff and load it
+    let code = format!("{}506001600060ff3e60ff51", call_only());
+    let result = execute(&code, &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::from(0x42) << 248]);
+}
+
+#[test]
+fn returndatacopy_out_of_bounds_fails() {
+    let mut state = WorldState::new();
+    state.insert(address(CALLEE), contract("60426000526001601ff3", 0));
+
+    // ask for two bytes when only one came back
+    let code = format!("{}5060025f5f3e", call_only());
+    let result = execute(&code, &mut state);
+
+    assert!(!result.success);
+    assert_eq!(result.reason, HaltReason::ReturnDataOutOfBounds);
+    assert_eq!(result.gas_used, GAS_LIMIT);
+}
+
+#[test]
+fn revert_reason_bubbles_up() {
+    let mut state = WorldState::new();
+    state.insert(address(CALLEE), contract("60426000526001601ffd", 0));
+
+    // copy the whole revert data to memory and revert with it
+    let code = format!("{}503d5f5f3e3d5ffd", call_only());
+    let result = execute(&code, &mut state);
+
+    assert_eq!(result.reason, HaltReason::Revert);
+    assert_eq!(result.return_data, vec![0x42]);
+}
@@ -0,0 +1,46 @@
+// helpers shared by the integration tests, every test binary compiles this
+// file but none of them uses all of it
+#![allow(dead_code)]
+
+use evm::opcode::PUSH32;
+use evm::{evm, Account, Address, Environment, EvmResult, WorldState};
+use primitive_types::U256;
+use proptest::prelude::*;
+
+pub const GAS_LIMIT: u64 = 1_000_000;
+
+// where the call tests put the code they call
+pub const CALLEE: &str = "1000000000000000000000000000000000000c42";
+
+// run code with an empty environment and state
+pub fn execute(code: &[u8]) -> EvmResult {
+    evm(code, GAS_LIMIT, &Environment::default(), &mut WorldState::new())
+}
+
+pub fn word(value: U256) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    bytes
+}
+
+// PUSH32 value
+pub fn push(value: U256) -> Vec<u8> {
+    let mut code = vec![PUSH32];
+    code.extend(word(value));
+    code
+}
+
+pub fn any_u256() -> impl Strategy<Value = U256> {
+    any::<[u8; 32]>().prop_map(|bytes| U256::from_big_endian(&bytes))
+}
+
+pub fn address(hex: &str) -> Address {
+    Address::from_slice(&hex::decode(hex).unwrap())
+}
+
+// an account holding `code`, given in hex
+pub fn contract(code: &str, balance: u64) -> Account {
+    let mut account = Account::with_code(hex::decode(code).unwrap());
+    account.balance = U256::from(balance);
+    account
+}
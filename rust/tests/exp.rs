@@ -0,0 +1,74 @@
+mod common;
+
+use num_bigint::BigUint;
+use primitive_types::U256;
+use proptest::prelude::*;
+
+use common::{any_u256, execute, push, word};
+
+// PUSH32 exponent, PUSH32 base, EXP
+fn run_exp(base: U256, exponent: U256) -> U256 {
+    let mut code = push(exponent);
+    code.extend(push(base));
+    code.push(0x0a);
+
+    let result = execute(&code);
+    assert!(result.success);
+    result.stack[0]
+}
+
+fn reference_exp(base: U256, exponent: U256) -> U256 {
+    let modulus = BigUint::from(1u8) << 256;
+    let result = BigUint::from_bytes_be(&word(base))
+        .modpow(&BigUint::from_bytes_be(&word(exponent)), &modulus);
+
+    U256::from_big_endian(&result.to_bytes_be())
+}
+
+proptest! {
+    #[test]
+    fn exp_matches_reference(base in any_u256(), exponent in any_u256()) {
+        prop_assert_eq!(run_exp(base, exponent), reference_exp(base, exponent));
+    }
+
+    #[test]
+    fn exp_small_exponent_matches_reference(base in any_u256(), exponent in 0u64..300) {
+        let exponent = U256::from(exponent);
+        prop_assert_eq!(run_exp(base, exponent), reference_exp(base, exponent));
+    }
+}
+
+#[test]
+fn exp_zero_exponent_is_one() {
+    assert_eq!(run_exp(U256::zero(), U256::zero()), U256::one());
+    assert_eq!(run_exp(U256::MAX, U256::zero()), U256::one());
+}
+
+#[test]
+fn exp_zero_base_is_zero() {
+    assert_eq!(run_exp(U256::zero(), U256::from(5)), U256::zero());
+}
+
+#[test]
+fn exp_two_to_the_255() {
+    assert_eq!(run_exp(U256::from(2), U256::from(255)), U256::one() << 255);
+    assert_eq!(run_exp(U256::from(2), U256::from(256)), U256::zero());
+}
+
+#[test]
+fn exp_huge_exponent() {
+    let exponent = U256::one() << 200;
+    let base = U256::from(3);
+
+    assert_eq!(run_exp(base, exponent), reference_exp(base, exponent));
+}
+
+#[test]
+fn exp_charges_per_exponent_byte() {
+    // two PUSH32, EXP and 50 for each of the two exponent bytes
+    let mut code = push(U256::from(0x0100));
+    code.extend(push(U256::from(2)));
+    code.push(0x0a);
+
+    assert_eq!(execute(&code).gas_used, 3 + 3 + 10 + 100);
+}
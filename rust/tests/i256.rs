@@ -0,0 +1,87 @@
+use evm::I256;
+use primitive_types::U256;
+
+fn int(value: i64) -> I256 {
+    let magnitude = I256::from_u256(U256::from(value.unsigned_abs()));
+    if value < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+#[test]
+fn round_trips_through_u256() {
+    assert_eq!(I256::from_u256(U256::MAX).into_u256(), U256::MAX);
+    assert_eq!(int(-1).into_u256(), U256::MAX);
+    assert_eq!(int(5).into_u256(), U256::from(5));
+}
+
+#[test]
+fn neg_and_abs() {
+    assert_eq!(-int(7), int(-7));
+    assert_eq!(-int(-7), int(7));
+    assert_eq!(-I256::ZERO, I256::ZERO);
+    assert_eq!(-I256::MIN, I256::MIN);
+
+    assert_eq!(int(-7).abs(), U256::from(7));
+    assert_eq!(I256::MIN.abs(), U256::one() << 255);
+    assert_eq!(I256::MAX.abs(), (U256::one() << 255) - 1);
+}
+
+#[test]
+fn signed_compare() {
+    assert!(int(-1) < int(0));
+    assert!(int(-2) < int(-1));
+    assert!(int(1) > int(-1));
+    assert!(I256::MIN < I256::MAX);
+    assert!(I256::MIN < int(-1));
+    assert_eq!(int(-3).cmp(&int(-3)), std::cmp::Ordering::Equal);
+}
+
+#[test]
+fn division_rounds_towards_zero() {
+    assert_eq!(int(10) / int(3), int(3));
+    assert_eq!(int(-10) / int(3), int(-3));
+    assert_eq!(int(10) / int(-3), int(-3));
+    assert_eq!(int(-10) / int(-3), int(3));
+}
+
+#[test]
+fn division_edge_cases() {
+    assert_eq!(int(10) / I256::ZERO, I256::ZERO);
+    assert_eq!(I256::MIN / int(-1), I256::MIN);
+    assert_eq!(I256::MIN / int(1), I256::MIN);
+    assert_eq!(I256::MAX / int(-1), -I256::MAX);
+}
+
+#[test]
+fn remainder_takes_sign_of_dividend() {
+    assert_eq!(int(10) % int(3), int(1));
+    assert_eq!(int(-10) % int(3), int(-1));
+    assert_eq!(int(10) % int(-3), int(1));
+    assert_eq!(int(-10) % int(-3), int(-1));
+    assert_eq!(int(-10) % I256::ZERO, I256::ZERO);
+    assert_eq!(I256::MIN % int(-1), I256::ZERO);
+}
+
+#[test]
+fn arithmetic_shift_right() {
+    assert_eq!(int(-16).sar(U256::from(2)), int(-4));
+    assert_eq!(int(-1).sar(U256::from(100)), int(-1));
+    assert_eq!(int(16).sar(U256::from(2)), int(4));
+    assert_eq!(int(-16).sar(U256::zero()), int(-16));
+    assert_eq!(I256::MIN.sar(U256::from(255)), int(-1));
+    assert_eq!(I256::MIN.sar(U256::MAX), int(-1));
+    assert_eq!(I256::MAX.sar(U256::from(256)), I256::ZERO);
+}
+
+#[test]
+fn displays_sign() {
+    assert_eq!(int(-42).to_string(), "-42");
+    assert_eq!(int(42).to_string(), "42");
+    assert_eq!(
+        I256::MIN.to_string(),
+        "-57896044618658097711785492504343953926634992332820282019728792003956564819968"
+    );
+}
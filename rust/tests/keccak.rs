@@ -0,0 +1,31 @@
+use evm::keccak256;
+
+#[test]
+fn empty_input() {
+    assert_eq!(
+        hex::encode(keccak256(b"")),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+}
+
+#[test]
+fn short_input() {
+    assert_eq!(
+        hex::encode(keccak256(b"abc")),
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+    );
+}
+
+#[test]
+fn input_longer_than_one_block() {
+    // 200 bytes spans two 136 byte rate blocks
+    assert_eq!(
+        hex::encode(keccak256(&[0xa3; 200])),
+        "3a57666b048777f2c953dc4456f45a2588e1cb6f2da760122d530ac2ce607d4a"
+    );
+}
+
+#[test]
+fn function_selector() {
+    assert_eq!(&keccak256(b"transfer(address,uint256)")[..4], &[0xa9, 0x05, 0x9c, 0xbb]);
+}
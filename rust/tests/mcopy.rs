@@ -0,0 +1,71 @@
+mod common;
+
+use evm::opcode::*;
+use primitive_types::U256;
+
+use common::{execute, push};
+
+// MSTORE the word 0x0102..20 at 0, MCOPY, then MLOAD the word at 0
+fn copy_and_load(destination: u8, source: u8, len: u8) -> U256 {
+    let pattern = U256::from_big_endian(&(1..=32).collect::<Vec<u8>>());
+
+    let mut code = push(pattern);
+    code.extend([PUSH1, 0x00, MSTORE]);
+    code.extend([PUSH1, len, PUSH1, source, PUSH1, destination, MCOPY]);
+    code.extend([PUSH1, 0x00, MLOAD]);
+
+    let result = execute(&code);
+    assert!(result.success);
+    result.stack[0]
+}
+
+fn word(bytes: &[u8]) -> U256 {
+    U256::from_big_endian(bytes)
+}
+
+#[test]
+fn overlapping_copy_forward() {
+    // bytes 0..4 copied one place up, as if through a temporary buffer
+    let mut expected: Vec<u8> = (1..=32).collect();
+    expected[1..5].copy_from_slice(&[1, 2, 3, 4]);
+
+    assert_eq!(copy_and_load(1, 0, 4), word(&expected));
+}
+
+#[test]
+fn overlapping_copy_backward() {
+    // bytes 1..5 copied one place down
+    let mut expected: Vec<u8> = (1..=32).collect();
+    expected[0..4].copy_from_slice(&[2, 3, 4, 5]);
+
+    assert_eq!(copy_and_load(0, 1, 4), word(&expected));
+}
+
+#[test]
+fn zero_length_at_huge_offsets_is_free() {
+    let mut code = push(U256::zero());
+    code.extend(push(U256::MAX));
+    code.extend(push(U256::MAX));
+    code.extend([MCOPY, MSIZE]);
+
+    let result = execute(&code);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::zero()]);
+    // three PUSH32, MCOPY and MSIZE
+    assert_eq!(result.gas_used, 3 * 3 + 3 + 2);
+}
+
+#[test]
+fn charges_per_word_and_for_expansion() {
+    // copy one word from 0 to 32 on empty memory
+    let result = execute(&[PUSH1, 32, PUSH1, 0, PUSH1, 32, MCOPY]);
+
+    assert!(result.success);
+    // three PUSH1, MCOPY, one word copied, two words of new memory
+    assert_eq!(result.gas_used, 3 * 3 + 3 + 3 + 6);
+
+    // 33 bytes touch a second word to copy and a third word of memory
+    let result = execute(&[PUSH1, 33, PUSH1, 0, PUSH1, 32, MCOPY]);
+    assert_eq!(result.gas_used, 3 * 3 + 3 + 6 + 9);
+}
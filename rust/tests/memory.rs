@@ -0,0 +1,101 @@
+mod common;
+
+use evm::opcode::*;
+use evm::{EvmResult, HaltReason};
+use primitive_types::U256;
+
+use common::{execute, push, GAS_LIMIT};
+
+fn huge_offsets() -> [U256; 3] {
+    [U256::MAX, U256::one() << 64, U256::from(u64::MAX)]
+}
+
+fn assert_fails(result: &EvmResult, reason: HaltReason) {
+    assert!(!result.success);
+    assert_eq!(result.reason, reason);
+    assert_eq!(result.gas_used, GAS_LIMIT);
+}
+
+#[test]
+fn mload_at_huge_offset_fails() {
+    for offset in huge_offsets() {
+        let mut code = push(offset);
+        code.push(MLOAD);
+
+        assert_fails(&execute(&code), HaltReason::MemoryOverflow);
+    }
+}
+
+#[test]
+fn mstore_at_huge_offset_fails() {
+    for offset in huge_offsets() {
+        let mut code = push(U256::one());
+        code.extend(push(offset));
+        code.push(MSTORE);
+
+        assert_fails(&execute(&code), HaltReason::MemoryOverflow);
+    }
+}
+
+#[test]
+fn calldatacopy_with_huge_length_fails() {
+    // lengths that do not fit in usize overflow, one that does is refused
+    // for its copy cost before any memory is touched
+    let cases = [
+        (U256::MAX, HaltReason::MemoryOverflow),
+        (U256::one() << 64, HaltReason::MemoryOverflow),
+        (U256::one() << 32, HaltReason::OutOfGas),
+    ];
+
+    for (len, reason) in cases {
+        let mut code = push(len);
+        code.extend(push(U256::zero()));
+        code.extend(push(U256::zero()));
+        code.push(CALLDATACOPY);
+
+        assert_fails(&execute(&code), reason);
+    }
+}
+
+#[test]
+fn log_with_huge_length_fails() {
+    // lengths whose data cost does not fit in u64 run out of gas like any
+    // other unaffordable log
+    let cases = [
+        (U256::MAX, HaltReason::MemoryOverflow),
+        (U256::one() << 64, HaltReason::MemoryOverflow),
+        (U256::one() << 62, HaltReason::OutOfGas),
+        (U256::one() << 61, HaltReason::OutOfGas),
+    ];
+
+    for (len, reason) in cases {
+        let mut code = push(len);
+        code.extend(push(U256::zero()));
+        code.push(LOG0);
+
+        assert_fails(&execute(&code), reason);
+    }
+}
+
+#[test]
+fn zero_length_copy_at_huge_offset_is_free() {
+    // CALLDATACOPY of nothing to U256::MAX, then MSIZE
+    let mut code = push(U256::zero());
+    code.extend(push(U256::zero()));
+    code.extend(push(U256::MAX));
+    code.extend([CALLDATACOPY, MSIZE]);
+
+    let result = execute(&code);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::zero()]);
+}
+
+#[test]
+fn unaffordable_expansion_runs_out_of_gas() {
+    // within the memory cap, but the quadratic cost is far above the limit
+    let mut code = push(U256::one() << 24);
+    code.push(MLOAD);
+
+    assert_fails(&execute(&code), HaltReason::OutOfGas);
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use evm::opcode::*;
+use num_bigint::BigUint;
+use primitive_types::U256;
+use proptest::prelude::*;
+
+use common::{any_u256, execute, push, word};
+
+// PUSH32 n, PUSH32 b, PUSH32 a, <opcode>
+fn run(opcode: u8, a: U256, b: U256, n: U256) -> U256 {
+    let mut code = Vec::new();
+    for value in [n, b, a] {
+        code.extend(push(value));
+    }
+    code.push(opcode);
+
+    let result = execute(&code);
+    assert!(result.success);
+    result.stack[0]
+}
+
+fn big(value: U256) -> BigUint {
+    BigUint::from_bytes_be(&word(value))
+}
+
+fn reference(opcode: u8, a: U256, b: U256, n: U256) -> U256 {
+    if n.is_zero() {
+        return U256::zero();
+    }
+
+    let result = match opcode {
+        ADDMOD => (big(a) + big(b)) % big(n),
+        _ => (big(a) * big(b)) % big(n),
+    };
+    U256::from_big_endian(&result.to_bytes_be())
+}
+
+proptest! {
+    #[test]
+    fn addmod_matches_reference(a in any_u256(), b in any_u256(), n in any_u256()) {
+        prop_assert_eq!(run(ADDMOD, a, b, n), reference(ADDMOD, a, b, n));
+    }
+
+    #[test]
+    fn mulmod_matches_reference(a in any_u256(), b in any_u256(), n in any_u256()) {
+        prop_assert_eq!(run(MULMOD, a, b, n), reference(MULMOD, a, b, n));
+    }
+}
+
+#[test]
+fn zero_modulus_gives_zero() {
+    for opcode in [ADDMOD, MULMOD] {
+        let ten = U256::from(10);
+        assert_eq!(run(opcode, ten, ten, U256::zero()), U256::zero());
+        assert_eq!(run(opcode, U256::MAX, U256::MAX, U256::zero()), U256::zero());
+    }
+}
+
+#[test]
+fn addmod_keeps_the_carry() {
+    // (2^256 - 1) + 2 = 2^256 + 1, which is 2 mod (2^256 - 1)
+    assert_eq!(run(ADDMOD, U256::MAX, U256::from(2), U256::MAX), U256::from(2));
+
+    let n = U256::from(7);
+    assert_eq!(run(ADDMOD, U256::MAX, U256::MAX, n), reference(ADDMOD, U256::MAX, U256::MAX, n));
+}
+
+#[test]
+fn mulmod_with_large_modulus() {
+    // (2^256 - 1)^2 mod (2^256 - 2) = 1
+    let n = U256::MAX - 1;
+    assert_eq!(run(MULMOD, U256::MAX, U256::MAX, n), U256::one());
+
+    let n = U256::one() << 255;
+    assert_eq!(run(MULMOD, n + 1, n + 1, n), U256::one());
+}
+
+#[test]
+fn modulus_of_one_gives_zero() {
+    for opcode in [ADDMOD, MULMOD] {
+        assert_eq!(run(opcode, U256::MAX, U256::MAX, U256::one()), U256::zero());
+    }
+}
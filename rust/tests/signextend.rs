@@ -0,0 +1,89 @@
+mod common;
+
+use evm::opcode::*;
+use primitive_types::U256;
+
+use common::{execute, push};
+
+// PUSH32 value, PUSH32 index, SIGNEXTEND
+fn signextend(index: U256, value: U256) -> U256 {
+    let mut code = push(value);
+    code.extend(push(index));
+    code.push(SIGNEXTEND);
+
+    let result = execute(&code);
+    assert!(result.success);
+    result.stack[0]
+}
+
+fn hex(value: &str) -> U256 {
+    U256::from_str_radix(value, 16).unwrap()
+}
+
+// the bytes 0x01..=0x20, nothing in it is zero or all ones
+fn pattern() -> U256 {
+    U256::from_big_endian(&(1..=32).collect::<Vec<u8>>())
+}
+
+#[test]
+fn index_one_negative() {
+    let expected = (U256::MAX << 16) | hex("80ff");
+
+    assert_eq!(signextend(U256::one(), hex("80ff")), expected);
+    // whatever sits above the sign byte is replaced
+    assert_eq!(signextend(U256::one(), (pattern() << 16) | hex("80ff")), expected);
+}
+
+#[test]
+fn index_one_positive() {
+    assert_eq!(signextend(U256::one(), hex("7fff")), hex("7fff"));
+    assert_eq!(signextend(U256::one(), (U256::MAX << 16) | hex("7f12")), hex("7f12"));
+}
+
+#[test]
+fn every_index_below_31() {
+    for index in 0..31usize {
+        let bits = 8 * (index + 1);
+        let low = U256::MAX >> (256 - bits);
+        let sign = U256::one() << (bits - 1);
+
+        // sign bit set, everything above it must become ones
+        let negative = (pattern() & low) | sign;
+        assert_eq!(
+            signextend(U256::from(index), negative),
+            negative | !low,
+            "index {}",
+            index
+        );
+
+        // sign bit clear, everything above it must become zeros
+        let positive = (pattern() | !low) & !sign;
+        assert_eq!(
+            signextend(U256::from(index), positive),
+            positive & low,
+            "index {}",
+            index
+        );
+    }
+}
+
+#[test]
+fn index_thirty() {
+    // byte 30 is 0x80, the top byte is garbage
+    let value = (hex("1280") << 240) | (pattern() >> 16);
+    assert_eq!(signextend(U256::from(30), value), (hex("ff80") << 240) | (pattern() >> 16));
+
+    let value = (hex("ff7f") << 240) | (pattern() >> 16);
+    assert_eq!(signextend(U256::from(30), value), (hex("7f") << 240) | (pattern() >> 16));
+}
+
+#[test]
+fn index_31_and_above_is_a_no_op() {
+    let values = [pattern(), U256::MAX, U256::one() << 255, (U256::one() << 255) - 1];
+
+    for index in [U256::from(31), U256::from(32), U256::MAX] {
+        for value in values {
+            assert_eq!(signextend(index, value), value, "index {}", index);
+        }
+    }
+}
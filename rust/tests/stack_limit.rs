@@ -0,0 +1,37 @@
+mod common;
+
+use evm::opcode::*;
+use evm::HaltReason;
+
+use common::{execute, GAS_LIMIT};
+
+#[test]
+fn full_stack_is_allowed() {
+    let result = execute(&[PUSH0; 1024]);
+
+    assert!(result.success);
+    assert_eq!(result.stack.len(), 1024);
+}
+
+#[test]
+fn push_past_the_limit_overflows() {
+    let result = execute(&[PUSH0; 1025]);
+
+    assert!(!result.success);
+    assert_eq!(result.reason, HaltReason::StackOverflow);
+    assert_eq!(result.pc, 1024);
+    assert_eq!(result.gas_used, GAS_LIMIT);
+}
+
+#[test]
+fn dup_on_a_full_stack_overflows() {
+    let mut code = vec![PUSH0; 1024];
+    code.push(DUP1);
+
+    let result = execute(&code);
+
+    assert!(!result.success);
+    assert_eq!(result.reason, HaltReason::StackOverflow);
+    assert_eq!(result.pc, 1024);
+    assert_eq!(result.gas_used, GAS_LIMIT);
+}
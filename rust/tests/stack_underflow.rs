@@ -0,0 +1,63 @@
+mod common;
+
+use evm::opcode::*;
+use evm::HaltReason;
+use primitive_types::U256;
+
+use common::{execute, GAS_LIMIT};
+
+fn assert_underflow(code: &[u8], pc: usize) {
+    let result = execute(code);
+
+    assert!(!result.success, "{:02x?} should fail", code);
+    assert_eq!(result.reason, HaltReason::StackUnderflow, "{:02x?}", code);
+    assert_eq!(result.pc, pc, "{:02x?}", code);
+    assert_eq!(result.gas_used, GAS_LIMIT, "{:02x?}", code);
+}
+
+#[test]
+fn binary_op_on_empty_stack() {
+    assert_underflow(&[ADD], 0);
+}
+
+#[test]
+fn binary_op_with_one_item() {
+    assert_underflow(&[PUSH1, 0x01, ADD], 2);
+}
+
+#[test]
+fn swap_with_too_few_items() {
+    // SWAPn needs n + 1 items, give it n
+    for n in 1..=16u8 {
+        let mut code = Vec::new();
+        for _ in 0..n {
+            code.extend([PUSH1, 0x01]);
+        }
+        code.push(SWAP1 + n - 1);
+
+        assert_underflow(&code, 2 * n as usize);
+    }
+}
+
+#[test]
+fn dup_with_too_few_items() {
+    // DUPn needs n items, give it n - 1
+    for n in 1..=16u8 {
+        let mut code = Vec::new();
+        for _ in 1..n {
+            code.extend([PUSH1, 0x01]);
+        }
+        code.push(DUP1 + n - 1);
+
+        assert_underflow(&code, 2 * (n as usize - 1));
+    }
+}
+
+#[test]
+fn underflow_leaves_the_stack_untouched() {
+    // PUSH1 1, PUSH1 2, SWAP2
+    let result = execute(&[PUSH1, 0x01, PUSH1, 0x02, SWAP1 + 1]);
+
+    assert_eq!(result.reason, HaltReason::StackUnderflow);
+    assert_eq!(result.stack, vec![U256::from(2), U256::one()]);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use evm::opcode::*;
+use primitive_types::U256;
+
+use common::execute;
+
+#[test]
+fn truncated_push_is_zero_padded() {
+    for size in 1..=32u8 {
+        // PUSHn 0x01 with only the first immediate byte present
+        let result = execute(&[PUSH1 + size - 1, 0x01]);
+
+        assert!(result.success, "PUSH{} should succeed", size);
+        assert_eq!(
+            result.stack,
+            vec![U256::one() << (8 * (size as usize - 1))],
+            "PUSH{}",
+            size
+        );
+    }
+}
+
+#[test]
+fn push_without_immediate_pushes_zero() {
+    for size in 1..=32u8 {
+        let result = execute(&[PUSH1 + size - 1]);
+
+        assert!(result.success, "PUSH{} should succeed", size);
+        assert_eq!(result.stack, vec![U256::zero()], "PUSH{}", size);
+    }
+}
+
+#[test]
+fn complete_push_is_unchanged() {
+    for size in 1..=32u8 {
+        let mut code = vec![PUSH1 + size - 1];
+        code.extend(std::iter::repeat_n(0xff, size as usize));
+
+        let result = execute(&code);
+        let expected = if size == 32 {
+            U256::MAX
+        } else {
+            (U256::one() << (8 * size as usize)) - 1
+        };
+
+        assert!(result.success, "PUSH{} should succeed", size);
+        assert_eq!(result.stack, vec![expected], "PUSH{}", size);
+    }
+}
+
+#[test]
+fn push32_at_end_of_code() {
+    // PUSH1 0x02, PUSH32 0x01
+    let result = execute(&[PUSH1, 0x02, PUSH32, 0x01]);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::one() << 248, U256::from(2)]);
+    assert_eq!(result.pc, 4);
+}
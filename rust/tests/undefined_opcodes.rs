@@ -0,0 +1,88 @@
+mod common;
+
+use evm::HaltReason;
+use primitive_types::U256;
+
+use common::{execute, GAS_LIMIT};
+
+// every byte that halts as an invalid opcode: the ones the instruction set
+// leaves unassigned, and the ones it assigns that are not implemented here
+const UNDEFINED: &[(u8, u8)] = &[
+    (0x0c, 0x0f),
+    (0x1e, 0x1f),
+    (0x21, 0x2f),
+    // BLOBHASH and BLOBBASEFEE
+    (0x49, 0x4a),
+    (0x4b, 0x4f),
+    // TLOAD and TSTORE
+    (0x5c, 0x5d),
+    (0xa5, 0xef),
+    // CREATE
+    (0xf0, 0xf0),
+    // CREATE2
+    (0xf5, 0xf5),
+    (0xf6, 0xf9),
+    (0xfb, 0xfc),
+    // SELFDESTRUCT
+    (0xff, 0xff),
+];
+
+fn undefined_opcodes() -> impl Iterator<Item = u8> {
+    UNDEFINED.iter().flat_map(|&(start, end)| start..=end)
+}
+
+#[test]
+fn undefined_opcode_halts() {
+    for opcode in undefined_opcodes() {
+        let result = execute(&[opcode]);
+
+        assert!(!result.success, "0x{:02x} should fail", opcode);
+        assert_eq!(result.reason, HaltReason::InvalidOpcode(opcode));
+        assert_eq!(result.pc, 0);
+        assert_eq!(result.gas_used, GAS_LIMIT);
+    }
+}
+
+#[test]
+fn undefined_opcode_stops_execution_midway() {
+    for opcode in undefined_opcodes() {
+        // PUSH1 1, <opcode>, PUSH1 2
+        let result = execute(&[0x60, 0x01, opcode, 0x60, 0x02]);
+
+        assert!(!result.success, "0x{:02x} should fail", opcode);
+        assert_eq!(result.reason, HaltReason::InvalidOpcode(opcode));
+        assert_eq!(result.pc, 2);
+        assert_eq!(result.stack, vec![U256::one()]);
+    }
+}
+
+#[test]
+fn every_other_opcode_is_defined() {
+    let undefined: Vec<u8> = undefined_opcodes().collect();
+
+    for opcode in 0..=0xffu8 {
+        if opcode == 0xfe || undefined.contains(&opcode) {
+            continue;
+        }
+
+        let result = execute(&[opcode]);
+        assert_ne!(result.reason, HaltReason::InvalidOpcode(opcode), "0x{:02x}", opcode);
+    }
+}
+
+#[test]
+fn designated_invalid_halts_like_undefined() {
+    let result = execute(&[0xfe]);
+
+    assert!(!result.success);
+    assert_eq!(result.reason, HaltReason::InvalidOpcode(0xfe));
+}
+
+#[test]
+fn undefined_byte_inside_push_data_is_not_executed() {
+    // PUSH1 0x0c
+    let result = execute(&[0x60, 0x0c]);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::from(0x0c)]);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use evm::opcode::*;
+use evm::{evm, evm_unmetered, Environment, EvmResult, HaltReason, WorldState};
+use primitive_types::U256;
+
+use common::{address, contract, execute, CALLEE, GAS_LIMIT};
+
+fn run(code: &[u8], state: &mut WorldState) -> EvmResult {
+    evm_unmetered(code, &Environment::default(), state)
+}
+
+#[test]
+fn gas_pushes_max() {
+    let result = run(&[GAS], &mut WorldState::new());
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::MAX]);
+    assert_eq!(result.gas_used, 0);
+}
+
+#[test]
+fn exceptional_halt_uses_no_gas() {
+    let result = run(&[INVALID], &mut WorldState::new());
+
+    assert_eq!(result.reason, HaltReason::InvalidOpcode(INVALID));
+    assert_eq!(result.gas_used, 0);
+}
+
+#[test]
+fn call_with_zero_gas_still_runs_the_callee() {
+    let callee = address(CALLEE);
+    let mut state = WorldState::new();
+    // MSTORE 0x42 at 0, RETURN the last byte of the word
+    state.insert(callee, contract("60426000526001601ff3", 0));
+
+    // the evm.json CALL case, which passes 0 as the gas
+    let code = hex::decode(format!("6001601f60006000600073{}6000f1600051", CALLEE)).unwrap();
+
+    let result = run(&code, &mut state.clone());
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::from(0x42), U256::one()]);
+
+    // metered, the callee runs out of gas on its first instruction
+    let result = evm(&code, GAS_LIMIT, &Environment::default(), &mut state);
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::zero(), U256::zero()]);
+}
+
+#[test]
+fn metered_execution_is_unchanged() {
+    // GAS after GAS
+    let result = execute(&[GAS, GAS]);
+
+    assert_eq!(
+        result.stack,
+        vec![U256::from(GAS_LIMIT - 4), U256::from(GAS_LIMIT - 2)]
+    );
+}
+
+#[test]
+fn staticcall_write_fails_on_the_static_check() {
+    let callee = address(CALLEE);
+    let mut state = WorldState::new();
+    // SSTORE 0x42 at slot 0
+    state.insert(callee, contract("6042600055", 0));
+
+    // the evm.json STATICCALL (reverts on write) case, unmetered so the
+    // callee cannot fail for lack of gas
+    let code = hex::decode(format!("6001601f6000600073{}6000fa", CALLEE)).unwrap();
+    let result = run(&code, &mut state.clone());
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::zero()]);
+
+    // the same write through CALL goes through
+    let code = hex::decode(format!("6001601f60006000600073{}6000f1", CALLEE)).unwrap();
+    let result = run(&code, &mut state);
+
+    assert!(result.success);
+    assert_eq!(result.stack, vec![U256::one()]);
+    assert_eq!(state.storage(&callee, U256::zero()), U256::from(0x42));
+}
+
+#[test]
+fn self_call_stops_at_the_depth_limit_on_a_small_stack() {
+    let callee = address(CALLEE);
+    let mut state = WorldState::new();
+    // call itself, then store one more than the counter its callee left in
+    // slot 0, the call at the limit is rejected
+    state.insert(callee, contract("5f5f5f5f5f305af1505f546001015f55", 0));
+
+    // far less stack than 1024 frames need, execution brings its own
+    let handle = std::thread::Builder::new()
+        .stack_size(256 << 10)
+        .spawn(move || {
+            let code = hex::decode(format!("5f5f5f5f5f73{}5af1", CALLEE)).unwrap();
+            let result = run(&code, &mut state);
+
+            assert!(result.success);
+            state.storage(&callee, U256::zero())
+        })
+        .unwrap();
+
+    assert_eq!(handle.join().unwrap(), U256::from(1024));
+}